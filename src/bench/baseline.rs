@@ -0,0 +1,233 @@
+//! Guard against regressions by comparing a [Report] with a saved baseline
+
+use std::fmt::Display;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BenchError, WResult};
+
+use super::report::{Report, Summary};
+
+/// How much worse than the [Baseline] a [Report] may be before it counts as a regression
+///
+/// Both values are absolute: with the default, the average may be 0.05 guesses per game higher
+/// and the win ratio 1 percentage point lower than before.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// how many guesses per game the average may rise
+    pub avg_steps: f64,
+    /// how much the win ratio may drop, from 0 to 1
+    pub win_ratio: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            avg_steps: 0.05,
+            win_ratio: 0.01,
+        }
+    }
+}
+
+/// The [Summary] of a [Report], saved under a name to compare later runs with
+///
+/// The baseline is stored as JSON, so it can be kept in the repository next to the solver code.
+///
+/// ## Example
+///
+/// ```
+/// use wordle_analyzer::bench::baseline::{Baseline, Tolerance};
+/// use wordle_analyzer::bench::builtin::BuiltinBenchmark;
+/// use wordle_analyzer::bench::{Benchmark, Targets};
+/// use wordle_analyzer::game::Game;
+/// use wordle_analyzer::solve::{NaiveSolver, Solver};
+/// use wordle_analyzer::wlist::builtin::BuiltinWList;
+///
+/// let wl = BuiltinWList::default();
+/// let solver = NaiveSolver::build(&wl).unwrap();
+/// let bench = BuiltinBenchmark::build(&wl, solver, Game::builder(&wl), 1).unwrap();
+/// let targets = Targets::Seeded { n: 10, seed: 7 };
+///
+/// let baseline = Baseline::of("naive", &bench.run(&targets).unwrap());
+/// let check = baseline.check(&bench.run(&targets).unwrap(), &Tolerance::default());
+/// assert!(check.passed());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    name: String,
+    summary: Summary,
+}
+
+impl Baseline {
+    /// Make a baseline named `name` from a finished [Report]
+    pub fn of(name: impl Into<String>, report: &Report) -> Self {
+        Self {
+            name: name.into(),
+            summary: report.summary(),
+        }
+    }
+
+    /// the name the baseline was saved with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// the statistics of the report the baseline was made from
+    pub fn summary(&self) -> &Summary {
+        &self.summary
+    }
+
+    /// Write the baseline to a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can not be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> WResult<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Read a baseline from a JSON file, as written by [save](Baseline::save)
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can not be read or is not a baseline.
+    pub fn load(path: impl AsRef<Path>) -> WResult<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Compare a new [Report] with the baseline
+    ///
+    /// The report regresses if it's average guesses per game or win ratio are worse than the
+    /// baseline by more than the [Tolerance]. Differences in the configuration, like another
+    /// wordlist or other targets, are noted in the [BaselineCheck], but do not fail it.
+    pub fn check(&self, report: &Report, tolerance: &Tolerance) -> BaselineCheck {
+        let current = report.summary();
+        let mut regressions = Vec::new();
+        if current.avg_steps > self.summary.avg_steps + tolerance.avg_steps {
+            regressions.push(format!(
+                "avg_steps rose from {:.4} to {:.4} (tolerance {})",
+                self.summary.avg_steps, current.avg_steps, tolerance.avg_steps
+            ));
+        }
+        if current.win_ratio < self.summary.win_ratio - tolerance.win_ratio {
+            regressions.push(format!(
+                "win_ratio dropped from {:.2}% to {:.2}% (tolerance {} points)",
+                self.summary.win_ratio * 100.0,
+                current.win_ratio * 100.0,
+                tolerance.win_ratio * 100.0
+            ));
+        }
+
+        let mut mismatches = Vec::new();
+        let mut note = |what: &str, before: String, after: String| {
+            if before != after {
+                mismatches.push(format!("{what} was {before}, now {after}"));
+            }
+        };
+        let (b, c) = (&self.summary, &current);
+        note("solver", b.solver.clone(), c.solver.clone());
+        note("wordlist", b.wordlist.clone(), c.wordlist.clone());
+        note("targets", b.targets.clone(), c.targets.clone());
+        note("n", b.n.to_string(), c.n.to_string());
+        note("length", b.length.to_string(), c.length.to_string());
+        note(
+            "max_steps",
+            b.max_steps.to_string(),
+            c.max_steps.to_string(),
+        );
+        note(
+            "hard_mode",
+            b.hard_mode.to_string(),
+            c.hard_mode.to_string(),
+        );
+        note(
+            "adversarial",
+            b.adversarial.to_string(),
+            c.adversarial.to_string(),
+        );
+        note("boards", b.boards.to_string(), c.boards.to_string());
+
+        BaselineCheck {
+            name: self.name.clone(),
+            baseline: self.summary.clone(),
+            current,
+            regressions,
+            mismatches,
+        }
+    }
+}
+
+/// The result of comparing a [Report] with a [Baseline], see [Baseline::check]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineCheck {
+    name: String,
+    baseline: Summary,
+    current: Summary,
+    regressions: Vec<String>,
+    mismatches: Vec<String>,
+}
+
+impl BaselineCheck {
+    /// did the report keep up with the baseline?
+    pub fn passed(&self) -> bool {
+        self.regressions.is_empty()
+    }
+
+    /// what got worse than the tolerance allows
+    pub fn regressions(&self) -> &[String] {
+        &self.regressions
+    }
+
+    /// how the configuration of the report differs from the one of the baseline
+    pub fn mismatches(&self) -> &[String] {
+        &self.mismatches
+    }
+
+    /// the statistics of the baseline
+    pub fn baseline(&self) -> &Summary {
+        &self.baseline
+    }
+
+    /// the statistics of the new report
+    pub fn current(&self) -> &Summary {
+        &self.current
+    }
+
+    /// Turn a failed check into an error
+    ///
+    /// # Errors
+    ///
+    /// Fails with [BenchError::Regression] if the check did not [pass](BaselineCheck::passed).
+    pub fn into_result(self) -> WResult<Self> {
+        if self.passed() {
+            Ok(self)
+        } else {
+            Err(BenchError::Regression(self.name.clone(), self.regressions.join(", ")).into())
+        }
+    }
+}
+
+impl Display for BaselineCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "baseline '{}': avg_steps {:.4} -> {:.4} ({:+.4}), win_ratio {:.2}% -> {:.2}% ({:+.2})",
+            self.name,
+            self.baseline.avg_steps,
+            self.current.avg_steps,
+            self.current.avg_steps - self.baseline.avg_steps,
+            self.baseline.win_ratio * 100.0,
+            self.current.win_ratio * 100.0,
+            (self.current.win_ratio - self.baseline.win_ratio) * 100.0,
+        )?;
+        for mismatch in &self.mismatches {
+            writeln!(f, "note: {mismatch}")?;
+        }
+        for regression in &self.regressions {
+            writeln!(f, "regression: {regression}")?;
+        }
+        write!(f, "{}", if self.passed() { "passed" } else { "FAILED" })
+    }
+}
@@ -1,8 +1,7 @@
 use std::sync::atomic::AtomicBool;
-use std::sync::{Arc, Mutex, RwLock};
-use std::thread::JoinHandle;
+use std::sync::{Arc, RwLock};
 
-use libpt::log::{debug, info};
+use libpt::log::info;
 
 use crate::error::WResult;
 use crate::game::{self, GameBuilder};
@@ -17,7 +16,24 @@ pub struct BuiltinBenchmark<'wl, WL: WordList, SL: Solver<'wl, WL>> {
     builder: GameBuilder<'wl, WL>,
     report: Arc<RwLock<Report>>,
     finished: AtomicBool,
-    bench_th: Arc<Mutex<Option<JoinHandle<WResult<Report>>>>>, // HACK: this is unholy
+    cancelled: AtomicBool,
+    boards: usize,
+    threads: usize,
+}
+
+impl<'wl, WL: WordList, SL: Solver<'wl, WL>> BuiltinBenchmark<'wl, WL, SL> {
+    /// Play games with multiple boards, like Quordle with 4 boards
+    ///
+    /// # Errors
+    ///
+    /// Fails if the games can not be built, for example because `boards` is 0.
+    pub fn with_boards(mut self, boards: usize) -> WResult<Self> {
+        self.report = Arc::new(RwLock::new(
+            Report::new_multi(self.builder.build_multi(boards)?).with_solver(self.solver.name()),
+        ));
+        self.boards = boards;
+        Ok(self)
+    }
 }
 
 impl<'wl, WL, SL> Benchmark<'wl, WL, SL> for BuiltinBenchmark<'wl, WL, SL>
@@ -34,16 +50,32 @@ where
         builder: GameBuilder<'wl, WL>,
         threads: usize,
     ) -> crate::error::WResult<Self> {
-        info!("using {threads} threads for benchmarking");
+        if threads == 0 {
+            info!("using the default amount of threads for benchmarking");
+        } else {
+            info!("using {threads} threads for benchmarking");
+        }
         Ok(Self {
+            report: Arc::new(RwLock::new(
+                Report::new(builder.build()?).with_solver(solver.name()),
+            )),
             solver,
-            report: Arc::new(RwLock::new(Report::new(builder.build()?))),
             builder,
             finished: AtomicBool::new(false),
-            bench_th: Arc::new(Mutex::new(None)),
+            cancelled: AtomicBool::new(false),
+            boards: 1,
+            threads,
         })
     }
     #[inline]
+    fn boards(&self) -> usize {
+        self.boards
+    }
+    #[inline]
+    fn threads(&self) -> usize {
+        self.threads
+    }
+    #[inline]
     fn solver(&self) -> SL {
         self.solver.clone()
     }
@@ -72,17 +104,17 @@ where
     fn is_finished(&self) -> bool {
         self.finished.load(std::sync::atomic::Ordering::Relaxed)
     }
-    fn start(&'wl self, n: usize, builder: &'wl GameBuilder<'wl, WL>) -> WResult<()> {
-        let report = self.report_shared();
-        let solver = self.solver();
-        // TODO: make this run in another thread somehow
-        self.bench(n, report, solver, builder)?;
-        debug!("finisihed the benchmark");
-        Ok(())
-    }
     fn set_finished(&self, value: bool) -> WResult<()> {
         self.finished
             .store(value, std::sync::atomic::Ordering::Relaxed);
         Ok(())
     }
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(std::sync::atomic::Ordering::Relaxed)
+    }
+    fn set_cancelled(&self, value: bool) -> WResult<()> {
+        self.cancelled
+            .store(value, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
 }
@@ -0,0 +1,367 @@
+//! Compare the [Reports](Report) of multiple solvers that played the same games
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::error::{BenchError, WResult};
+use crate::wlist::word::Word;
+
+use super::report::{score, Report, Z_95};
+
+/// How many of the words where the challenger did worst are shown when a [Comparison] is displayed
+pub const SHOWN_REGRESSIONS: usize = 5;
+
+/// The [Reports](Report) of multiple solvers for the same [Targets](super::Targets)
+///
+/// The first report is the baseline, every other report is [paired](Comparison::paired) with it
+/// game by game. For this, the reports need to have played the same solutions in the same order,
+/// so the targets must be [reproducible](super::Targets::reproducible).
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    reports: Vec<Report>,
+}
+
+impl Comparison {
+    /// Compare finished reports, the first one is the baseline
+    ///
+    /// # Errors
+    ///
+    /// Fails with [BenchError::NotComparable] if there are no reports, one of them is not
+    /// finished, or they did not play the same games.
+    pub fn new(reports: Vec<Report>) -> WResult<Self> {
+        let Some(baseline) = reports.first() else {
+            return Err(not_comparable("there are no reports"));
+        };
+        for report in &reports {
+            if !report.finished() {
+                return Err(not_comparable(&format!(
+                    "the report of {} is not finished",
+                    report.solver()
+                )));
+            }
+            let same_games = report.n() == baseline.n()
+                && report
+                    .outcomes()
+                    .iter()
+                    .zip(baseline.outcomes())
+                    .all(|(a, b)| a.solution() == b.solution());
+            if !same_games {
+                return Err(not_comparable(&format!(
+                    "{} and {} did not play the same games",
+                    baseline.solver(),
+                    report.solver()
+                )));
+            }
+        }
+        Ok(Self { reports })
+    }
+
+    /// the compared reports, the baseline first
+    pub fn reports(&self) -> &[Report] {
+        &self.reports
+    }
+
+    /// the report everything is compared with
+    pub fn baseline(&self) -> &Report {
+        &self.reports[0]
+    }
+
+    /// Compare the report at `index` with the [baseline](Comparison::baseline) game by game
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no report at `index`.
+    pub fn paired(&self, index: usize) -> PairedComparison {
+        let baseline = self.baseline();
+        let challenger = &self.reports[index];
+        PairedComparison {
+            baseline: baseline.solver().to_string(),
+            challenger: challenger.solver().to_string(),
+            differences: baseline
+                .outcomes()
+                .iter()
+                .zip(challenger.outcomes())
+                .map(|(b, c)| Difference {
+                    solution: b.solution(),
+                    baseline: score(b, baseline.max_steps()),
+                    challenger: score(c, challenger.max_steps()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Compare every report after the first with the [baseline](Comparison::baseline)
+    pub fn pairs(&self) -> Vec<PairedComparison> {
+        (1..self.reports.len()).map(|i| self.paired(i)).collect()
+    }
+
+    /// Export every single game of every report as CSV, like [Report::to_csv]
+    #[cfg(feature = "serde")]
+    pub fn to_csv(&self) -> WResult<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for row in self.reports.iter().flat_map(|r| r.game_rows()) {
+            writer.serialize(row)?;
+        }
+        super::report::csv_string(writer)
+    }
+
+    /// Export the [summary](Report::summary) of every report as CSV, one row per report
+    #[cfg(feature = "serde")]
+    pub fn summaries_to_csv(&self) -> WResult<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for report in &self.reports {
+            writer.serialize(report.summary())?;
+        }
+        super::report::csv_string(writer)
+    }
+
+    /// Export the [summaries](Report::summary) and the statistics of the [pairs](Comparison::pairs)
+    /// as JSON
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> WResult<String> {
+        #[derive(Serialize)]
+        struct Export {
+            reports: Vec<super::report::Summary>,
+            pairs: Vec<PairSummary>,
+        }
+        let export = Export {
+            reports: self.reports.iter().map(|r| r.summary()).collect(),
+            pairs: self.pairs().iter().map(|p| p.summary()).collect(),
+        };
+        Ok(serde_json::to_string_pretty(&export)?)
+    }
+}
+
+/// How a challenger did against the baseline in a single game
+///
+/// The scores are the guesses needed, lost games count as one guess more than allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Difference {
+    pub solution: Option<Word>,
+    pub baseline: usize,
+    pub challenger: usize,
+}
+
+impl Difference {
+    /// how many guesses the challenger needed more than the baseline, negative if less
+    pub fn delta(&self) -> i64 {
+        self.challenger as i64 - self.baseline as i64
+    }
+}
+
+/// Two solvers compared game by game, see [Comparison::paired]
+///
+/// Whether the challenger is better is decided with a paired t-test on the
+/// [differences](Difference::delta). For the usual benchmark sizes of more than 30 games, the
+/// normal distribution is close enough to the t-distribution, so the difference is called
+/// [significant](PairedComparison::significant) at the 95% level if the
+/// [t-statistic](PairedComparison::t_statistic) is further from 0 than [Z_95].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairedComparison {
+    baseline: String,
+    challenger: String,
+    differences: Vec<Difference>,
+}
+
+impl PairedComparison {
+    /// the name of the baseline solver
+    pub fn baseline(&self) -> &str {
+        &self.baseline
+    }
+
+    /// the name of the solver that is compared with the baseline
+    pub fn challenger(&self) -> &str {
+        &self.challenger
+    }
+
+    /// the [Difference] of each game
+    pub fn differences(&self) -> &[Difference] {
+        &self.differences
+    }
+
+    /// in how many games the challenger needed less guesses
+    pub fn better(&self) -> usize {
+        self.differences.iter().filter(|d| d.delta() < 0).count()
+    }
+
+    /// in how many games the challenger needed more guesses
+    pub fn worse(&self) -> usize {
+        self.differences.iter().filter(|d| d.delta() > 0).count()
+    }
+
+    /// in how many games both needed the same amount of guesses
+    pub fn ties(&self) -> usize {
+        self.differences.iter().filter(|d| d.delta() == 0).count()
+    }
+
+    /// how many guesses the challenger needed more per game on average, negative if less
+    pub fn mean_difference(&self) -> f64 {
+        if self.differences.is_empty() {
+            return 0.0;
+        }
+        self.differences
+            .iter()
+            .map(|d| d.delta() as f64)
+            .sum::<f64>()
+            / self.differences.len() as f64
+    }
+
+    /// the sample standard deviation of the differences
+    pub fn std_dev(&self) -> f64 {
+        let n = self.differences.len();
+        if n < 2 {
+            return 0.0;
+        }
+        let mean = self.mean_difference();
+        let variance = self
+            .differences
+            .iter()
+            .map(|d| (d.delta() as f64 - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1) as f64;
+        variance.sqrt()
+    }
+
+    /// The t-statistic of the paired t-test
+    ///
+    /// This is 0 if the solvers did the same in every game, and [None] if the challenger was
+    /// better or worse by the same amount in every game, as the differences do not vary then.
+    pub fn t_statistic(&self) -> Option<f64> {
+        let mean = self.mean_difference();
+        if mean == 0.0 {
+            return Some(0.0);
+        }
+        let std_dev = self.std_dev();
+        if std_dev == 0.0 {
+            return None;
+        }
+        Some(mean / (std_dev / (self.differences.len() as f64).sqrt()))
+    }
+
+    /// Is the difference between the solvers significant at the 95% level?
+    ///
+    /// A difference that is the same in every one of multiple games is always significant.
+    pub fn significant(&self) -> bool {
+        self.differences.len() > 1 && self.t_statistic().is_none_or(|t| t.abs() > Z_95)
+    }
+
+    /// The `n` games where the challenger did worst compared with the baseline
+    pub fn regressions(&self, n: usize) -> Vec<Difference> {
+        let mut regressions: Vec<Difference> = self
+            .differences
+            .iter()
+            .filter(|d| d.delta() > 0)
+            .copied()
+            .collect();
+        regressions.sort_by(|a, b| b.delta().cmp(&a.delta()).then(a.solution.cmp(&b.solution)));
+        regressions.truncate(n);
+        regressions
+    }
+
+    /// the statistics of the comparison, without the single games
+    pub fn summary(&self) -> PairSummary {
+        PairSummary {
+            baseline: self.baseline.clone(),
+            challenger: self.challenger.clone(),
+            better: self.better(),
+            worse: self.worse(),
+            ties: self.ties(),
+            mean_difference: self.mean_difference(),
+            std_dev: self.std_dev(),
+            t_statistic: self.t_statistic(),
+            significant: self.significant(),
+        }
+    }
+}
+
+/// The statistics of a [PairedComparison]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PairSummary {
+    pub baseline: String,
+    pub challenger: String,
+    pub better: usize,
+    pub worse: usize,
+    pub ties: usize,
+    pub mean_difference: f64,
+    pub std_dev: f64,
+    pub t_statistic: Option<f64>,
+    pub significant: bool,
+}
+
+impl Display for PairedComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} vs {}: better {}, worse {}, ties {}, {:+.4} guesses per game (t = {}, {})",
+            self.challenger,
+            self.baseline,
+            self.better(),
+            self.worse(),
+            self.ties(),
+            self.mean_difference(),
+            match self.t_statistic() {
+                Some(t) => format!("{t:.2}"),
+                None => "-".to_string(),
+            },
+            if self.significant() {
+                "significant"
+            } else {
+                "not significant"
+            }
+        )?;
+        let regressions = self.regressions(SHOWN_REGRESSIONS);
+        if !regressions.is_empty() {
+            write!(f, "\n  worse at:")?;
+            for d in regressions {
+                match d.solution {
+                    Some(s) => write!(f, " {s} ({:+})", d.delta())?,
+                    None => write!(f, " ? ({:+})", d.delta())?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .reports
+            .iter()
+            .map(|r| r.solver().len())
+            .max()
+            .unwrap_or(0)
+            .max("solver".len());
+        writeln!(
+            f,
+            "{:<width$} {:>6} {:>8} {:>8} {:>19} {:>7} {:>4} {:>10}",
+            "solver", "n", "win", "score", "95% CI", "median", "p90", "time/game"
+        )?;
+        for r in &self.reports {
+            let (low, high) = r.avg_score_interval();
+            writeln!(
+                f,
+                "{:<width$} {:>6} {:>7.2}% {:>8.4} {:>19} {:>7} {:>4} {:>8}μs",
+                r.solver(),
+                r.n(),
+                r.avg_win() * 100.0,
+                r.avg_score(),
+                format!("{low:.4} - {high:.4}"),
+                r.median_steps(),
+                r.percentile_steps(90.0),
+                r.avg_time().num_microseconds().unwrap_or(i64::MAX),
+            )?;
+        }
+        for pair in self.pairs() {
+            write!(f, "\n{pair}")?;
+        }
+        Ok(())
+    }
+}
+
+fn not_comparable(msg: &str) -> crate::error::Error {
+    BenchError::NotComparable(msg.to_string()).into()
+}
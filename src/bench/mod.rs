@@ -1,7 +1,8 @@
 use std::fmt::Debug;
 use std::sync::{Arc, RwLock};
+use std::thread::{Scope, ScopedJoinHandle};
 
-use libpt::log::debug;
+use libpt::log::{debug, warn};
 use rayon::prelude::*;
 
 use crate::error::WResult;
@@ -13,6 +14,17 @@ use crate::wlist::WordList;
 pub mod report;
 use report::*;
 
+pub mod targets;
+pub use targets::Targets;
+
+pub mod progress;
+pub use progress::Progress;
+
+pub mod compare;
+
+#[cfg(feature = "builtin")]
+pub mod baseline;
+
 #[cfg(feature = "builtin")]
 pub mod builtin;
 
@@ -39,12 +51,20 @@ where
     }
     fn solver(&'wl self) -> SL;
     fn solver_ref(&'wl self) -> &'wl SL;
+    /// How many boards each game has, see [MultiGame](crate::game::multi::MultiGame)
+    fn boards(&self) -> usize {
+        1
+    }
     fn play(&'wl self) -> WResult<GuessResponse> {
         self.solver_ref().play(&mut self.make_game()?)
     }
-    // TODO: add some interface to get reports while the benchmark runs
-    // TODO: make the benchmark optionally multithreaded
-    // NOTE: This is blocking, use start to let it run in another thread
+    /// How many threads play the games, 0 lets [rayon] decide
+    fn threads(&self) -> usize {
+        0
+    }
+    /// Play `n` games with random solutions, blocking until they are played
+    ///
+    /// Use [start](Benchmark::start) to let the games be played in the background.
     fn bench(
         &self,
         n: usize,
@@ -52,25 +72,193 @@ where
         solver: SL,
         builder: &'wl GameBuilder<'wl, WL>,
     ) -> WResult<Report> {
-        (0..n)
-            .into_par_iter()
-            .for_each_with(report.clone(), |outside_data, _i| {
-                let report = outside_data;
-                let r = solver
-                    .play_owned(builder.build().expect("could not create game"))
-                    .expect("error playing the game during benchmark");
-                report.write().expect("lock is poisoned").add(r);
-            });
-        libpt::log::info!("finished playing games, finalizing report");
-        report.write().expect("lock is poisoned").finalize();
+        self.bench_targets(&Targets::Random(n), report, solver, builder)
+    }
+    /// Play a game for each of the [Targets] and collect the results in the [Report]
+    ///
+    /// The games are played in parallel with [threads](Benchmark::threads) threads, but the
+    /// [outcomes](Report::outcomes) of the finished report are in the order of the targets. If
+    /// the benchmark is [cancelled](Benchmark::cancel), the games that were not started yet are
+    /// skipped and the report holds the games played so far.
+    ///
+    /// This is blocking, use [start_targets](Benchmark::start_targets) to let the games be
+    /// played in the background.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the [solutions](Targets::solutions) of the targets can not be found, the games
+    /// can not be built or the threads can not be started. If the solver fails to play a game,
+    /// no more games are started and the error is returned, the report is left unfinished.
+    fn bench_targets(
+        &self,
+        targets: &Targets,
+        report: Arc<RwLock<Report>>,
+        solver: SL,
+        builder: &'wl GameBuilder<'wl, WL>,
+    ) -> WResult<Report> {
+        let boards = self.boards();
+        let example = builder.build()?;
+        let adversarial = example.adversarial();
+        if adversarial && targets.reproducible() {
+            warn!("adversarial games choose their own solutions, the targets are ignored");
+        }
+        let solutions = targets.solutions(example.wordlist(), example.length(), boards)?;
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.threads())
+            .build()?;
+        report
+            .write()
+            .expect("lock is poisoned")
+            .begin(targets, solutions.len().div_ceil(boards));
+        let played = pool.install(|| {
+            solutions.par_chunks(boards).enumerate().try_for_each_with(
+                report.clone(),
+                |report, (idx, solutions)| -> WResult<()> {
+                    if self.is_cancelled() {
+                        return Ok(());
+                    }
+                    let start = std::time::Instant::now();
+                    if boards > 1 {
+                        let mut game = builder.build_multi(boards)?;
+                        for (board, solution) in solutions.iter().enumerate() {
+                            if solution.is_some() && !adversarial {
+                                game.set_solution(board, *solution);
+                            }
+                        }
+                        solver.play_multi(&mut game)?;
+                        report.write().expect("lock is poisoned").add_multi_at(
+                            idx,
+                            &game,
+                            Some(elapsed(start)),
+                        );
+                    } else {
+                        let mut game = builder.build()?;
+                        if solutions[0].is_some() && !adversarial {
+                            game.set_solution(solutions[0]);
+                        }
+                        let r = solver.play_owned(game)?;
+                        report.write().expect("lock is poisoned").add_at(
+                            idx,
+                            r,
+                            Some(elapsed(start)),
+                        );
+                    }
+                    Ok(())
+                },
+            )
+        });
+        if let Err(err) = played {
+            libpt::log::error!("could not play a game of the benchmark: {err}");
+            self.set_finished(true)?;
+            return Err(err);
+        }
+        let mut report = report.write().expect("lock is poisoned");
+        if self.is_cancelled() {
+            libpt::log::info!("the benchmark was cancelled, finalizing report");
+            report.set_cancelled(true);
+        } else {
+            libpt::log::info!("finished playing games, finalizing report");
+        }
+        report.finalize();
         debug!("finalized the report");
         self.set_finished(true)?;
-        Ok(report.read().expect("lock is poisoned").clone())
+        Ok(report.clone())
     }
+    /// A snapshot of the [Report], which can be read while the benchmark runs
     // PERF: Somehow returning &Report would be better as we don't need to clone then
     fn report(&'wl self) -> Report;
     fn report_shared(&'wl self) -> Arc<RwLock<Report>>;
-    fn start(&'wl self, n: usize, builder: &'wl GameBuilder<'wl, WL>) -> WResult<()>;
+    /// How far the benchmark is, see [Progress]
+    fn progress(&'wl self) -> Progress {
+        self.report_shared()
+            .read()
+            .expect("lock is poisoned")
+            .progress()
+    }
+    /// Play a game for each of the [Targets], blocking until they are played
+    fn run(&'wl self, targets: &Targets) -> WResult<Report> {
+        self.set_finished(false)?;
+        self.set_cancelled(false)?;
+        self.bench_targets(
+            targets,
+            self.report_shared(),
+            self.solver(),
+            self.builder_ref(),
+        )
+    }
+    /// Play `n` games with random solutions in the background, see
+    /// [start_targets](Benchmark::start_targets)
+    fn start<'scope>(
+        &'wl self,
+        scope: &'scope Scope<'scope, 'wl>,
+        n: usize,
+    ) -> WResult<ScopedJoinHandle<'scope, WResult<Report>>> {
+        self.start_targets(scope, &Targets::Random(n))
+    }
+    /// Play a game for each of the [Targets] in a thread of `scope`
+    ///
+    /// While the games are played, the [progress](Benchmark::progress) and a snapshot of the
+    /// [report](Benchmark::report) can be read, and the benchmark can be
+    /// [cancelled](Benchmark::cancel). Joining the returned handle gives the finished report.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use wordle_analyzer::bench::builtin::BuiltinBenchmark;
+    /// use wordle_analyzer::bench::{Benchmark, Targets};
+    /// use wordle_analyzer::game::Game;
+    /// use wordle_analyzer::solve::{NaiveSolver, Solver};
+    /// use wordle_analyzer::wlist::builtin::BuiltinWList;
+    ///
+    /// let wl = BuiltinWList::default();
+    /// let solver = NaiveSolver::build(&wl).unwrap();
+    /// let bench = BuiltinBenchmark::build(&wl, solver, Game::builder(&wl), 2).unwrap();
+    /// let report = std::thread::scope(|s| {
+    ///     let handle = bench.start_targets(s, &Targets::Random(10)).unwrap();
+    ///     while !handle.is_finished() {
+    ///         println!("{}", bench.progress());
+    ///         std::thread::sleep(std::time::Duration::from_millis(10));
+    ///     }
+    ///     handle.join().unwrap()
+    /// })
+    /// .unwrap();
+    /// assert_eq!(report.n(), 10);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the thread can not be spawned. Errors while playing are returned when joining.
+    fn start_targets<'scope>(
+        &'wl self,
+        scope: &'scope Scope<'scope, 'wl>,
+        targets: &Targets,
+    ) -> WResult<ScopedJoinHandle<'scope, WResult<Report>>> {
+        self.set_finished(false)?;
+        self.set_cancelled(false)?;
+        let targets = targets.clone();
+        Ok(std::thread::Builder::new()
+            .name("benchmark".to_string())
+            .spawn_scoped(scope, move || {
+                let report = self.bench_targets(
+                    &targets,
+                    self.report_shared(),
+                    self.solver(),
+                    self.builder_ref(),
+                );
+                debug!("finished the benchmark");
+                report
+            })?)
+    }
     fn is_finished(&self) -> bool;
     fn set_finished(&self, value: bool) -> WResult<()>;
+    /// Stop the benchmark, the games that are already started are still played
+    fn cancel(&self) -> WResult<()> {
+        self.set_cancelled(true)
+    }
+    fn is_cancelled(&self) -> bool;
+    fn set_cancelled(&self, value: bool) -> WResult<()>;
+}
+
+fn elapsed(start: std::time::Instant) -> chrono::TimeDelta {
+    chrono::TimeDelta::from_std(start.elapsed()).expect("a game can not take that long")
 }
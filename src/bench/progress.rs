@@ -0,0 +1,106 @@
+//! How far a running [Benchmark](super::Benchmark) is
+
+use std::fmt::Display;
+
+use chrono::TimeDelta;
+
+/// A snapshot of how many games of a [Benchmark](super::Benchmark) are played
+///
+/// Get it with [Benchmark::progress](super::Benchmark::progress) or
+/// [Report::progress](super::report::Report::progress) while the benchmark runs in the
+/// background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    done: usize,
+    total: usize,
+    elapsed: TimeDelta,
+    finished: bool,
+    cancelled: bool,
+}
+
+impl Progress {
+    pub(crate) fn new(
+        done: usize,
+        total: usize,
+        elapsed: TimeDelta,
+        finished: bool,
+        cancelled: bool,
+    ) -> Self {
+        Self {
+            done,
+            total,
+            elapsed,
+            finished,
+            cancelled,
+        }
+    }
+
+    /// how many games are played
+    pub fn done(&self) -> usize {
+        self.done
+    }
+
+    /// how many games will be played, 0 until the benchmark has started
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// how long the benchmark runs
+    pub fn elapsed(&self) -> TimeDelta {
+        self.elapsed
+    }
+
+    /// is the benchmark finished, either because all games are played or it was cancelled?
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// was the benchmark cancelled before all games were played?
+    pub fn cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// the share of the games that are played, from 0 to 1
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return if self.finished { 1.0 } else { 0.0 };
+        }
+        self.done as f64 / self.total as f64
+    }
+
+    /// How long the remaining games will probably take
+    ///
+    /// Estimated from the time the played games took. [None] until the first game is played.
+    pub fn eta(&self) -> Option<TimeDelta> {
+        if self.finished {
+            return Some(TimeDelta::zero());
+        }
+        if self.done == 0 {
+            return None;
+        }
+        let per_game = self.elapsed / self.done as i32;
+        Some(per_game * self.total.saturating_sub(self.done) as i32)
+    }
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{} games ({:.1}%), {}s elapsed",
+            self.done,
+            self.total,
+            self.fraction() * 100.0,
+            self.elapsed.num_seconds()
+        )?;
+        if self.cancelled {
+            write!(f, ", cancelled")
+        } else if self.finished {
+            write!(f, ", finished")
+        } else if let Some(eta) = self.eta() {
+            write!(f, ", about {}s left", eta.num_seconds())
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -1,25 +1,98 @@
 use chrono::{self, NaiveDateTime, TimeDelta};
 use libpt::log::debug;
-use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
+#[cfg(feature = "serde")]
+use crate::error::WResult;
+use crate::game::multi::MultiGame;
 use crate::game::response::GuessResponse;
 use crate::game::Game;
+use crate::wlist::word::Word;
 use crate::wlist::WordList;
 
+use super::progress::Progress;
+
 pub const WEIGHTING_STEPS: f64 = 1000.0;
 pub const WEIGHTING_TIME: f64 = 50.0;
 pub const WEIGHTING_WIN: f64 = 1000.0;
 
+/// z-score of the 95% confidence interval of a normal distribution
+pub const Z_95: f64 = 1.96;
+
+/// How many of the worst and slowest games are shown when a [Report] is displayed
+pub const SHOWN_OUTCOMES: usize = 5;
+
+/// the guesses needed for a game, a lost game counts as one more than allowed
+pub(crate) fn score(outcome: &Outcome, max_steps: usize) -> usize {
+    if outcome.won() {
+        outcome.steps()
+    } else {
+        max_steps + 1
+    }
+}
+
+/// How a single game of a benchmark went
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Outcome {
+    /// the position of the target of the game, games are played in parallel
+    index: usize,
+    won: bool,
+    /// how many guesses were made
+    steps: usize,
+    solution: Option<Word>,
+    time: Option<TimeDelta>,
+}
+
+impl Outcome {
+    /// was the game won?
+    pub fn won(&self) -> bool {
+        self.won
+    }
+
+    /// how many guesses were made
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// the solution of the game, if it is known
+    ///
+    /// Games with multiple boards have no single solution.
+    pub fn solution(&self) -> Option<Word> {
+        self.solution
+    }
+
+    /// how long it took to play the game, if it was measured
+    pub fn time(&self) -> Option<TimeDelta> {
+        self.time
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Report {
-    data: Vec<GuessResponse>,
+    data: Vec<Outcome>,
     start: NaiveDateTime,
     end: Option<NaiveDateTime>,
     benchtime: Option<TimeDelta>,
     /// is the benchmark finished?
     finished: bool,
+    /// was the benchmark cancelled before all games were played?
+    cancelled: bool,
+    /// how many games the benchmark plays
+    total: usize,
     max_steps: usize,
+    /// how many boards each game has
+    boards: usize,
+    /// the [name](crate::solve::Solver::name) of the solver
+    solver: String,
+    /// the [name](WordList::name) of the wordlist
+    wordlist: String,
+    /// which solutions were played, see [Targets](super::Targets)
+    targets: String,
+    length: usize,
+    hard_mode: bool,
+    adversarial: bool,
 }
 
 impl Report {
@@ -30,11 +103,74 @@ impl Report {
             benchtime: None,
             end: None,
             finished: false,
+            cancelled: false,
+            total: 0,
+            max_steps: example_game.max_steps(),
+            boards: 1,
+            solver: String::from("unknown"),
+            wordlist: example_game.wordlist().name().to_string(),
+            targets: super::Targets::Random(0).to_string(),
+            length: example_game.length(),
+            hard_mode: example_game.hard_mode(),
+            adversarial: example_game.adversarial(),
+        }
+    }
+    /// Name the solver that plays the games, see [Solver::name](crate::solve::Solver::name)
+    pub fn with_solver(mut self, solver: impl Into<String>) -> Self {
+        self.solver = solver.into();
+        self
+    }
+    /// Create a [Report] for games with multiple boards
+    pub fn new_multi<WL: WordList>(example_game: MultiGame<'_, WL>) -> Self {
+        Self {
             max_steps: example_game.max_steps(),
+            boards: example_game.boards().len(),
+            ..Self::new(example_game.boards()[0].clone())
         }
     }
     pub fn add(&mut self, data: GuessResponse) {
-        self.data.push(data)
+        self.add_at(self.data.len(), data, None)
+    }
+    /// Add a finished game together with the time it took to play it
+    pub fn add_timed(&mut self, data: GuessResponse, time: TimeDelta) {
+        self.add_at(self.data.len(), data, Some(time))
+    }
+    /// Add the finished game for the target with the `index`, see [finalize](Report::finalize)
+    pub(crate) fn add_at(&mut self, index: usize, data: GuessResponse, time: Option<TimeDelta>) {
+        self.data.push(Outcome {
+            index,
+            won: data.won(),
+            steps: data.step(),
+            solution: data.solution().map(|s| s.0),
+            time,
+        })
+    }
+    /// Add a finished game with multiple boards
+    pub fn add_multi<WL: WordList>(&mut self, game: &MultiGame<'_, WL>) {
+        self.add_multi_at(self.data.len(), game, None)
+    }
+    /// Like [add_at](Report::add_at), but for games with multiple boards
+    pub(crate) fn add_multi_at<WL: WordList>(
+        &mut self,
+        index: usize,
+        game: &MultiGame<'_, WL>,
+        time: Option<TimeDelta>,
+    ) {
+        self.data.push(Outcome {
+            index,
+            won: game.won(),
+            steps: game.step(),
+            solution: None,
+            time,
+        })
+    }
+
+    /// how each game went
+    ///
+    /// Once the report is [finished](Report::finished), the games are in the order of the
+    /// [Targets](super::Targets), before that in the order they were finished.
+    pub fn outcomes(&self) -> &[Outcome] {
+        &self.data
     }
 
     pub fn n(&self) -> usize {
@@ -44,7 +180,7 @@ impl Report {
     pub fn total_wins(&self) -> usize {
         let mut wins: usize = 0;
         self.data.iter().for_each(|d| {
-            if d.won() {
+            if d.won {
                 wins += 1;
             }
         });
@@ -55,21 +191,143 @@ impl Report {
         self.total_wins() as f64 / self.n() as f64
     }
 
+    /// how many guesses were made in all games together
+    ///
+    /// Lost games count with the guesses that were made, which is [max_steps](Report::max_steps).
     pub fn total_steps(&self) -> usize {
-        let mut steps: usize = 0;
-        self.data.iter().for_each(|d| steps += d.step() - 1);
-        steps
+        self.data.iter().map(|d| d.steps).sum()
     }
 
     pub fn avg_steps(&self) -> f64 {
         self.total_steps() as f64 / self.n() as f64
     }
 
+    /// The average guesses per game, where lost games count as one guess more than allowed
+    ///
+    /// Unlike [avg_steps](Report::avg_steps), a lost game is always worse than a won one. The
+    /// [interval](Report::avg_score_interval), median, percentiles and standard deviation are
+    /// based on this score.
+    pub fn avg_score(&self) -> f64 {
+        self.scores().sum::<usize>() as f64 / self.n() as f64
+    }
+
+    /// the [score] of each game
+    fn scores(&self) -> impl Iterator<Item = usize> + '_ {
+        self.data.iter().map(|d| score(d, self.max_steps))
+    }
+
+    /// How many games were won with each amount of guesses
+    ///
+    /// The first element holds the games won with one guess, the last the ones won with
+    /// [max_steps](Report::max_steps) guesses. Lost games are not counted, see
+    /// [total_wins](Report::total_wins).
+    pub fn distribution(&self) -> Vec<usize> {
+        let mut distribution = vec![0; self.max_steps];
+        for d in self.data.iter().filter(|d| d.won) {
+            if distribution.len() < d.steps {
+                distribution.resize(d.steps, 0);
+            }
+            distribution[d.steps - 1] += 1;
+        }
+        distribution
+    }
+
+    /// the [scores](score) of all games, sorted
+    fn sorted_scores(&self) -> Vec<usize> {
+        let mut scores: Vec<usize> = self.scores().collect();
+        scores.sort();
+        scores
+    }
+
+    /// The median of the guesses per game
+    ///
+    /// Lost games count as one guess more than allowed.
+    pub fn median_steps(&self) -> f64 {
+        let steps = self.sorted_scores();
+        if steps.is_empty() {
+            return f64::NAN;
+        }
+        let mid = steps.len() / 2;
+        if steps.len().is_multiple_of(2) {
+            (steps[mid - 1] + steps[mid]) as f64 / 2.0
+        } else {
+            steps[mid] as f64
+        }
+    }
+
+    /// The guesses that `percentile` percent of the games needed at most
+    ///
+    /// Lost games count as one guess more than allowed. `percentile` is clamped to 0 to 100.
+    /// Returns 0 if no games were played.
+    pub fn percentile_steps(&self, percentile: f64) -> usize {
+        let steps = self.sorted_scores();
+        if steps.is_empty() {
+            return 0;
+        }
+        // nearest rank
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * steps.len() as f64).ceil() as usize;
+        steps[rank.max(1) - 1]
+    }
+
+    /// The sample standard deviation of the guesses per game
+    ///
+    /// Lost games count as one guess more than allowed.
+    pub fn std_dev_steps(&self) -> f64 {
+        if self.n() < 2 {
+            return 0.0;
+        }
+        let avg = self.avg_score();
+        let variance =
+            self.scores().map(|s| (s as f64 - avg).powi(2)).sum::<f64>() / (self.n() - 1) as f64;
+        variance.sqrt()
+    }
+
+    /// The 95% confidence interval of the [average score](Report::avg_score)
+    ///
+    /// If the games were played again, the average would be in this interval with a chance of
+    /// 95%. Two solvers whose intervals overlap can not be told apart with this many games.
+    pub fn avg_score_interval(&self) -> (f64, f64) {
+        let avg = self.avg_score();
+        let margin = Z_95 * self.std_dev_steps() / (self.n() as f64).sqrt();
+        (avg - margin, avg + margin)
+    }
+
+    /// The `n` games that went worst, lost games first, then the ones with the most guesses
+    pub fn worst(&self, n: usize) -> Vec<Outcome> {
+        let mut outcomes = self.data.clone();
+        outcomes.sort_by(|a, b| {
+            a.won
+                .cmp(&b.won)
+                .then(b.steps.cmp(&a.steps))
+                .then(a.solution.cmp(&b.solution))
+        });
+        outcomes.truncate(n);
+        outcomes
+    }
+
+    /// The `n` games that took the longest to play, of those that were timed
+    pub fn slowest(&self, n: usize) -> Vec<Outcome> {
+        let mut outcomes: Vec<Outcome> = self
+            .data
+            .iter()
+            .filter(|d| d.time.is_some())
+            .copied()
+            .collect();
+        outcomes.sort_by_key(|o| std::cmp::Reverse(o.time));
+        outcomes.truncate(n);
+        outcomes
+    }
+
+    /// The average time it took to play a game, of those that were timed
+    ///
+    /// As games are played in parallel, this can be longer than the
+    /// [benchtime](Report::benchtime) divided by the games. Returns 0 if no game was timed.
     pub fn avg_time(&self) -> TimeDelta {
-        if self.n() == 0 {
-            return TimeDelta::new(0, 0).unwrap();
+        let times: Vec<TimeDelta> = self.data.iter().filter_map(|d| d.time).collect();
+        if times.is_empty() {
+            return TimeDelta::zero();
         }
-        self.benchtime() / self.n() as i32
+        times.iter().sum::<TimeDelta>() / times.len() as i32
     }
 
     fn rating_steps(&self) -> f64 {
@@ -104,9 +362,11 @@ impl Report {
 
     /// finalize the record
     ///
-    /// Sets the [benchtime](Report::benchtime) and [over](Report::over). In future versions, this
-    /// method might be used to precompute statistical information from the data.
+    /// Sets the [benchtime](Report::benchtime) and [over](Report::over), and puts the games back
+    /// into the order of their targets. In future versions, this method might be used to
+    /// precompute statistical information from the data.
     pub(crate) fn finalize(&mut self) {
+        self.data.sort_by_key(|o| o.index);
         self.end = Some(chrono::Local::now().naive_local());
         self.benchtime = Some(self.end.unwrap() - self.start);
         self.finished = true;
@@ -119,13 +379,226 @@ impl Report {
         self.finished
     }
 
+    /// how long the benchmark ran, or runs until now if it is not finished
     pub fn benchtime(&self) -> TimeDelta {
-        chrono::Local::now().naive_local() - self.start
+        self.benchtime
+            .unwrap_or_else(|| chrono::Local::now().naive_local() - self.start)
     }
 
     pub fn max_steps(&self) -> usize {
         self.max_steps
     }
+
+    /// how many boards each game of the benchmark has
+    pub fn boards(&self) -> usize {
+        self.boards
+    }
+
+    /// the name of the solver that played the games
+    pub fn solver(&self) -> &str {
+        &self.solver
+    }
+
+    /// the name of the wordlist the games were played with
+    pub fn wordlist(&self) -> &str {
+        &self.wordlist
+    }
+
+    /// which solutions were played, as described by [Targets](super::Targets)
+    pub fn targets(&self) -> &str {
+        &self.targets
+    }
+
+    /// Start the report over for a benchmark that plays `total` games for the `targets`
+    pub(crate) fn begin(&mut self, targets: &super::Targets, total: usize) {
+        self.data.clear();
+        self.targets = targets.to_string();
+        self.total = total;
+        self.start = chrono::Local::now().naive_local();
+        self.end = None;
+        self.benchtime = None;
+        self.finished = false;
+        self.cancelled = false;
+    }
+
+    /// was the benchmark cancelled before all games were played?
+    pub fn cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    pub(crate) fn set_cancelled(&mut self, cancelled: bool) {
+        self.cancelled = cancelled;
+    }
+
+    /// how many games the benchmark plays, [n](Report::n) are played already
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// How far the benchmark is, see [Progress]
+    pub fn progress(&self) -> Progress {
+        Progress::new(
+            self.n(),
+            self.total,
+            self.benchtime(),
+            self.finished,
+            self.cancelled,
+        )
+    }
+
+    /// how many letters the words have
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// were the games played in hard mode?
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// were the games played against an adversary?
+    pub fn adversarial(&self) -> bool {
+        self.adversarial
+    }
+
+    /// The configuration and aggregate statistics of the benchmark, without the single games
+    pub fn summary(&self) -> Summary {
+        let (avg_score_low, avg_score_high) = self.avg_score_interval();
+        Summary {
+            solver: self.solver.clone(),
+            wordlist: self.wordlist.clone(),
+            targets: self.targets.clone(),
+            length: self.length,
+            max_steps: self.max_steps,
+            hard_mode: self.hard_mode,
+            adversarial: self.adversarial,
+            boards: self.boards,
+            n: self.n(),
+            wins: self.total_wins(),
+            win_ratio: self.avg_win(),
+            avg_steps: self.avg_steps(),
+            avg_score: self.avg_score(),
+            avg_score_low,
+            avg_score_high,
+            std_dev_steps: self.std_dev_steps(),
+            median_steps: self.median_steps(),
+            p90_steps: self.percentile_steps(90.0),
+            p99_steps: self.percentile_steps(99.0),
+            avg_time_us: self.avg_time().num_microseconds().unwrap_or(i64::MAX),
+            benchtime_ms: self.benchtime().num_milliseconds(),
+            rating: self.rating(),
+        }
+    }
+
+    /// Export the full report as JSON
+    ///
+    /// This holds the [Summary], the [distribution](Report::distribution) and every single game.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> WResult<String> {
+        let export = Export {
+            summary: self.summary(),
+            distribution: self.distribution(),
+            games: self.game_rows().collect(),
+        };
+        Ok(serde_json::to_string_pretty(&export)?)
+    }
+
+    /// Export every single game as CSV, one row per game
+    ///
+    /// Each row names the solver and wordlist as well, so the rows of multiple reports can be
+    /// put into one table.
+    #[cfg(feature = "serde")]
+    pub fn to_csv(&self) -> WResult<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for row in self.game_rows() {
+            writer.serialize(row)?;
+        }
+        csv_string(writer)
+    }
+
+    /// Export the [Summary] as CSV, with a header and a single row
+    #[cfg(feature = "serde")]
+    pub fn summary_to_csv(&self) -> WResult<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.serialize(self.summary())?;
+        csv_string(writer)
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn game_rows(&self) -> impl Iterator<Item = GameRow<'_>> {
+        self.data.iter().map(|o| GameRow {
+            solver: &self.solver,
+            wordlist: &self.wordlist,
+            solution: o.solution.map(|s| s.to_string()),
+            won: o.won,
+            steps: o.steps,
+            time_us: o.time.and_then(|t| t.num_microseconds()),
+        })
+    }
+}
+
+/// The configuration and aggregate statistics of a [Report]
+///
+/// With the `serde` feature, this is serialized as a flat record, so it can be a row of a CSV
+/// table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Summary {
+    pub solver: String,
+    pub wordlist: String,
+    /// which solutions were played, see [Targets](super::Targets)
+    pub targets: String,
+    pub length: usize,
+    pub max_steps: usize,
+    pub hard_mode: bool,
+    pub adversarial: bool,
+    pub boards: usize,
+    /// how many games were played
+    pub n: usize,
+    pub wins: usize,
+    pub win_ratio: f64,
+    pub avg_steps: f64,
+    /// the average guesses per game, lost games count as one more than allowed
+    pub avg_score: f64,
+    /// lower end of the 95% confidence interval of `avg_score`
+    pub avg_score_low: f64,
+    /// upper end of the 95% confidence interval of `avg_score`
+    pub avg_score_high: f64,
+    pub std_dev_steps: f64,
+    pub median_steps: f64,
+    pub p90_steps: usize,
+    pub p99_steps: usize,
+    pub avg_time_us: i64,
+    pub benchtime_ms: i64,
+    pub rating: f64,
+}
+
+/// A single game, as it is exported
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize)]
+pub(crate) struct GameRow<'r> {
+    solver: &'r str,
+    wordlist: &'r str,
+    solution: Option<String>,
+    won: bool,
+    steps: usize,
+    time_us: Option<i64>,
+}
+
+/// The full report, as it is exported to JSON
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize)]
+struct Export<'r> {
+    #[serde(flatten)]
+    summary: Summary,
+    distribution: Vec<usize>,
+    games: Vec<GameRow<'r>>,
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn csv_string(writer: csv::Writer<Vec<u8>>) -> WResult<String> {
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(String::from_utf8(bytes).expect("CSV of strings is valid UTF-8"))
 }
 
 impl Display for Report {
@@ -135,21 +608,96 @@ impl Display for Report {
     ///
     /// This will panic if the [Report] is not finished
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "solver: {}, wordlist: {}, targets: {}, length: {}, max_steps: {}{}{}",
+            self.solver,
+            self.wordlist,
+            self.targets,
+            self.length,
+            self.max_steps,
+            if self.hard_mode { ", hard mode" } else { "" },
+            if self.adversarial {
+                ", adversarial"
+            } else {
+                ""
+            },
+        )?;
+        if self.boards > 1 {
+            writeln!(f, "boards: {}", self.boards)?;
+        }
+        if self.cancelled {
+            writeln!(f, "cancelled after {} of {} games", self.n(), self.total)?;
+        }
+        let (low, high) = self.avg_score_interval();
         write!(
             f,
             "n: {}\n\
             win_ratio: {:.2}%\n\
-            avg_score: {:.4}\n\
-            avgerage time per game: {}μs\n\
+            avg_steps: {:.4}\n\
+            avg_score: {:.4} (95% CI {:.4} - {:.4})\n\
+            std_dev: {:.4}\n\
+            median: {}, p90: {}, p99: {}\n\
+            average time per game: {}μs\n\
             full time until completion: {}ms\n\
             rating: {:.4}\n\
             ",
             self.n(),
             self.avg_win() * 100.0,
             self.avg_steps(),
-            self.avg_time(),
+            self.avg_score(),
+            low,
+            high,
+            self.std_dev_steps(),
+            self.median_steps(),
+            self.percentile_steps(90.0),
+            self.percentile_steps(99.0),
+            self.avg_time().num_microseconds().unwrap_or(i64::MAX),
+            self.benchtime().num_milliseconds(),
             self.rating(),
-            self.benchtime()
-        )
+        )?;
+
+        writeln!(f, "distribution:")?;
+        let distribution = self.distribution();
+        let lost = self.n() - self.total_wins();
+        let most = distribution
+            .iter()
+            .copied()
+            .chain([lost])
+            .max()
+            .unwrap_or(0);
+        let bar = |count: usize| "#".repeat((count * 40).div_ceil(most.max(1)));
+        for (idx, count) in distribution.iter().enumerate() {
+            writeln!(f, "{:>3}: {:<40} {count}", idx + 1, bar(*count))?;
+        }
+        writeln!(f, "{:>3}: {:<40} {lost}", "X", bar(lost))?;
+
+        let show = |o: &Outcome| match o.solution {
+            Some(s) => s.to_string(),
+            None => "?".to_string(),
+        };
+        let worst = self.worst(SHOWN_OUTCOMES);
+        if !worst.is_empty() {
+            write!(f, "worst:")?;
+            for o in worst {
+                let steps = if o.won {
+                    o.steps.to_string()
+                } else {
+                    "X".to_string()
+                };
+                write!(f, " {} ({steps})", show(&o))?;
+            }
+            writeln!(f)?;
+        }
+        let slowest = self.slowest(SHOWN_OUTCOMES);
+        if !slowest.is_empty() {
+            write!(f, "slowest:")?;
+            for o in slowest {
+                let time = o.time.expect("only timed games are slowest");
+                write!(f, " {} ({}ms)", show(&o), time.num_milliseconds())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,106 @@
+//! Which solutions the games of a [Benchmark](super::Benchmark) are played with
+
+use std::fmt::Display;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::error::{BenchError, GameError, WResult};
+use crate::wlist::word::{Word, WordData};
+use crate::wlist::WordList;
+
+/// The solutions a [Benchmark](super::Benchmark) plays games for
+///
+/// With [Random](Targets::Random), every run plays other games, so two reports can only be
+/// compared roughly. The other variants always give the same solutions in the same order, so
+/// reports are reproducible and the results of two solvers can be compared game by game.
+///
+/// For games with multiple boards, the solutions are split into groups, one for each game. If
+/// the last group is too small, the remaining boards get random solutions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Targets {
+    /// `n` random solutions, see [WordList::rand_solution]
+    Random(usize),
+    /// `n` solutions drawn from [WordList::solutions], the same `seed` gives the same solutions
+    ///
+    /// Solutions may be drawn more than once.
+    Seeded { n: usize, seed: u64 },
+    /// every word of [WordList::solutions] once, in alphabetical order
+    Exhaustive,
+    /// these words, once each, in the given order
+    Words(Vec<Word>),
+}
+
+impl Targets {
+    /// Get the solution of each board of each game, [None] where a random one should be used
+    ///
+    /// Only solutions with `length` letters are used.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [GameError::WordNotInWordlist] if a word of [Targets::Words] is not in `wl`, or
+    /// [BenchError::TargetHasWrongLength] if it does not have `length` letters.
+    pub fn solutions<WL: WordList>(
+        &self,
+        wl: &WL,
+        length: usize,
+        boards: usize,
+    ) -> WResult<Vec<Option<WordData>>> {
+        Ok(match self {
+            Self::Random(n) => vec![None; n * boards],
+            Self::Seeded { n, seed } => {
+                let solutions = sorted_solutions(wl, length);
+                let mut rng = StdRng::seed_from_u64(*seed);
+                (0..n * boards)
+                    .map(|_| solutions.choose(&mut rng).copied())
+                    .collect()
+            }
+            Self::Exhaustive => sorted_solutions(wl, length).into_iter().map(Some).collect(),
+            Self::Words(words) => words
+                .iter()
+                .map(|w| -> WResult<Option<WordData>> {
+                    if w.char_len() != length {
+                        return Err(BenchError::TargetHasWrongLength(*w, length).into());
+                    }
+                    Ok(Some(
+                        wl.get_word(w).ok_or(GameError::WordNotInWordlist(*w))?,
+                    ))
+                })
+                .collect::<WResult<_>>()?,
+        })
+    }
+
+    /// are the solutions the same for every run?
+    pub fn reproducible(&self) -> bool {
+        !matches!(self, Self::Random(_))
+    }
+}
+
+impl Default for Targets {
+    fn default() -> Self {
+        Self::Random(super::DEFAULT_N)
+    }
+}
+
+impl Display for Targets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Random(_) => write!(f, "random"),
+            Self::Seeded { seed, .. } => write!(f, "seed {seed}"),
+            Self::Exhaustive => write!(f, "all solutions"),
+            Self::Words(words) => write!(f, "{} given words", words.len()),
+        }
+    }
+}
+
+/// the solutions of the wordlist with `length` letters, in a stable order
+fn sorted_solutions<WL: WordList>(wl: &WL, length: usize) -> Vec<WordData> {
+    let mut solutions: Vec<WordData> = wl
+        .solutions()
+        .into_iter()
+        .filter(|s| s.0.char_len() == length)
+        .collect();
+    solutions.sort_by_key(|s| s.0);
+    solutions
+}
@@ -2,52 +2,145 @@
 // #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
 
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::thread::sleep;
+use std::time::{Duration, Instant};
 
 use clap::Parser;
 use libpt::log::*;
 
+use wordle_analyzer::bench::baseline::{Baseline, Tolerance};
 use wordle_analyzer::bench::builtin::BuiltinBenchmark;
-use wordle_analyzer::bench::{Benchmark, DEFAULT_N};
+use wordle_analyzer::bench::compare::Comparison;
+use wordle_analyzer::bench::report::Report;
+use wordle_analyzer::bench::{Benchmark, Targets, DEFAULT_N};
 use wordle_analyzer::game::GameBuilder;
+use wordle_analyzer::solve::tree::Objective;
 use wordle_analyzer::solve::{AnyBuiltinSolver, BuiltinSolverNames};
 use wordle_analyzer::wlist::builtin::BuiltinWList;
+use wordle_analyzer::wlist::word::Alphabet;
 
 use wordle_analyzer::{self, game};
 
+/// how often to check if the benchmark is finished
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// how often to print the progress of the benchmark
+const PRINT_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Parser, Clone, Debug)]
 #[command(version, about, long_about, author)]
 struct Cli {
     /// precompute all possibilities for better performance at runtime
     #[arg(short, long)]
     precompute: bool,
-    /// how long should the word be?
+    /// directory to store the precomputed possibilities in, so they only need to be computed once
+    ///
+    /// Only used with --precompute.
+    #[arg(long)]
+    precompute_cache: Option<std::path::PathBuf>,
+    /// how long should the word be? From 3 to 12 letters
     #[arg(short, long, default_value_t = wordle_analyzer::DEFAULT_WORD_LENGTH)]
     length: usize,
     /// how many times can we guess?
     #[arg(short, long, default_value_t = wordle_analyzer::DEFAULT_MAX_STEPS)]
     max_steps: usize,
+    /// play in hard mode: every guess must use all hints revealed so far
+    #[arg(long)]
+    hard: bool,
+    /// how many boards to play at once, like 4 for Quordle
+    ///
+    /// Each board after the first gives one extra guess.
+    #[arg(short, long, default_value_t = 1)]
+    boards: usize,
+    /// play against an adversary that avoids the solution as long as possible
+    ///
+    /// This shows how the solver does in the worst case.
+    #[arg(long)]
+    adversarial: bool,
     /// more verbose logs
     #[arg(short, long)]
     verbose: bool,
-    /// which solver to use
-    #[arg(short, long, default_value_t = BuiltinSolverNames::default())]
-    solver: BuiltinSolverNames,
+    /// which solvers to use, the default is the naive solver
+    ///
+    /// With more than one solver, all solvers play the same games and are compared with the
+    /// first one. This cannot be combined with --adversarial or --time-limit.
+    ///
+    /// Solvers can be configured, like 'tree:worst-case,breadth=4', see the documentation of the
+    /// decision tree solver for the options.
+    #[arg(short, long, num_args = 1..)]
+    solver: Vec<SolverSpec>,
     /// how many games to play for the benchmark
+    ///
+    /// Ignored if the targets are given with --all or --targets.
     #[arg(short, long, default_value_t = DEFAULT_N)]
     n: usize,
+    /// draw the solutions with this seed, so that every run plays the same games
+    #[arg(long)]
+    seed: Option<u64>,
+    /// play one game for every solution of the wordlist
+    #[arg(long, conflicts_with_all = ["seed", "targets"])]
+    all: bool,
+    /// play one game for every word in this file, the words are separated by whitespace
+    ///
+    /// With --transliterate, the words are transliterated like the wordlist.
+    #[arg(long, conflicts_with = "seed")]
+    targets: Option<PathBuf>,
+    /// stop each benchmark after this many seconds, and report the games played so far
+    #[arg(long)]
+    time_limit: Option<u64>,
     /// how many threads to use for benchmarking
     ///
-    /// Note that the application as the whole will use at least one more thread.
+    /// Note that the application as the whole will use at least one more thread. With 0, the
+    /// amount of threads is chosen automatically.
     #[arg(short, long, default_value_t = num_cpus::get())]
     threads: usize,
 
     /// select a wordlist
     ///
     /// 'ger' and 'eng' are special values bundled with this executable, if the value does not
-    /// match either of those, it will be assumed to be a file path.
+    /// match either of those, it will be assumed to be a file path. The format of the file is
+    /// detected, it can be JSON, a plain list of words, or a TSV or CSV table of words and their
+    /// counts. Prefix the path with the format to choose it, like 'ranked:words.txt' for a list
+    /// with the most common words first. Give two files separated by a comma to load the
+    /// words that can be guessed and the solutions separately.
     #[arg(short, long, default_value_t = String::from("eng"))]
     wordlist: String,
+    /// write the words with the letters a to z only, like 'ae' for 'ä' and 'ss' for 'ß'
+    #[arg(long)]
+    transliterate: bool,
+
+    /// directory to store decision trees in, so they only need to be built once
+    ///
+    /// Only used with the decisiontree solver.
+    #[arg(long)]
+    tree_cache: Option<std::path::PathBuf>,
+
+    /// write the full report with every game as JSON to this file
+    #[arg(long)]
+    json: Option<PathBuf>,
+    /// write every game as a row of CSV to this file
+    #[arg(long)]
+    csv: Option<PathBuf>,
+    /// write the configuration and aggregate statistics as CSV to this file
+    #[arg(long)]
+    summary_csv: Option<PathBuf>,
+
+    /// save the report as a baseline with this name, to compare later runs with it
+    #[arg(long)]
+    save_baseline: Option<String>,
+    /// compare the report with the baseline of this name, and fail if it regressed
+    #[arg(long)]
+    baseline: Option<String>,
+    /// directory the baselines are stored in
+    #[arg(long, default_value = "baselines")]
+    baseline_dir: PathBuf,
+    /// how many guesses per game the average may rise before it counts as a regression
+    #[arg(long, default_value_t = Tolerance::default().avg_steps)]
+    tolerance_steps: f64,
+    /// how many percentage points the win ratio may drop before it counts as a regression
+    #[arg(long, default_value_t = Tolerance::default().win_ratio * 100.0)]
+    tolerance_win: f64,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -59,28 +152,231 @@ fn main() -> anyhow::Result<()> {
     }
     trace!("dumping CLI: {:#?}", cli);
 
-    let wl = match cli.wordlist.as_str() {
-        "ger" => BuiltinWList::german(cli.length),
-        "eng" => BuiltinWList::english(cli.length),
-        _ => BuiltinWList::load(&cli.wordlist, cli.length)?,
-    };
+    let wl = BuiltinWList::select_with(&cli.wordlist, cli.length, alphabet(&cli))?;
     let builder: GameBuilder<'_, BuiltinWList> = game::Game::builder(&wl)
         .length(cli.length)
         .max_steps(cli.max_steps)
-        .precompute(cli.precompute);
-    let solver: AnyBuiltinSolver<'_, BuiltinWList> = cli.solver.to_solver(&wl);
-    let bench = BuiltinBenchmark::build(&wl, solver, builder, cli.threads)?;
-    trace!("{bench:#?}");
-
-    bench.start(cli.n, &bench.builder())?;
-
-    loop {
-        sleep(std::time::Duration::from_secs(1));
-        println!("{}", bench.report());
-        if bench.is_finished() {
-            break;
+        .hard_mode(cli.hard)
+        .adversarial(cli.adversarial)
+        .precompute(cli.precompute)
+        .precompute_cache(cli.precompute_cache.as_ref());
+    let mut targets = if cli.all {
+        Targets::Exhaustive
+    } else if let Some(path) = &cli.targets {
+        Targets::Words(
+            std::fs::read_to_string(path)?
+                .split_whitespace()
+                .map(|w| alphabet(&cli).word(w))
+                .collect::<Result<_, _>>()?,
+        )
+    } else if let Some(seed) = cli.seed {
+        Targets::Seeded { n: cli.n, seed }
+    } else {
+        Targets::Random(cli.n)
+    };
+    let specs = if cli.solver.is_empty() {
+        vec![SolverSpec::default()]
+    } else {
+        cli.solver.clone()
+    };
+    if specs.len() > 1 && (cli.baseline.is_some() || cli.save_baseline.is_some()) {
+        anyhow::bail!("baselines can only be used with a single solver");
+    }
+    // the solvers are compared game by game, so they need to play the same solutions to the end
+    if specs.len() > 1 && cli.adversarial {
+        anyhow::bail!(
+            "solvers cannot be compared in adversarial games, as these have no fixed solutions"
+        );
+    }
+    if specs.len() > 1 && cli.time_limit.is_some() {
+        anyhow::bail!(
+            "solvers cannot be compared with a time limit, as not all games would be played"
+        );
+    }
+    if cli.baseline.is_some() && !targets.reproducible() {
+        warn!(
+            "the targets are random, use --seed, --all or --targets to compare with a baseline \
+             reliably"
+        );
+    }
+    if specs.len() > 1 && !targets.reproducible() {
+        let seed = rand::random();
+        info!("using the seed {seed}, so that all solvers play the same games");
+        targets = Targets::Seeded { n: cli.n, seed };
+    }
+
+    let mut benches = Vec::with_capacity(specs.len());
+    for spec in &specs {
+        let mut bench =
+            BuiltinBenchmark::build(&wl, spec.to_solver(&wl, &cli), builder.clone(), cli.threads)?;
+        if cli.boards != 1 {
+            bench = bench.with_boards(cli.boards)?;
+        }
+        trace!("{bench:#?}");
+        benches.push(bench);
+    }
+
+    let mut reports = Vec::with_capacity(benches.len());
+    for bench in &benches {
+        let report = std::thread::scope(|s| -> anyhow::Result<Report> {
+            let start = Instant::now();
+            let handle = bench.start_targets(s, &targets)?;
+            let mut last_print = Instant::now();
+            while !handle.is_finished() {
+                sleep(POLL_INTERVAL);
+                if let Some(limit) = cli.time_limit {
+                    if !bench.is_cancelled() && start.elapsed().as_secs() >= limit {
+                        warn!("the time limit of {limit}s is reached, cancelling the benchmark");
+                        bench.cancel()?;
+                    }
+                }
+                if last_print.elapsed() >= PRINT_INTERVAL {
+                    let snapshot = bench.report();
+                    if snapshot.n() == 0 {
+                        println!("{}: {}", snapshot.solver(), snapshot.progress());
+                    } else {
+                        println!(
+                            "{}: {}, win_ratio: {:.2}%, avg_steps: {:.4}",
+                            snapshot.solver(),
+                            snapshot.progress(),
+                            snapshot.avg_win() * 100.0,
+                            snapshot.avg_steps()
+                        );
+                    }
+                    last_print = Instant::now();
+                }
+            }
+            Ok(handle.join().expect("the benchmark thread panicked")?)
+        })?;
+        println!("{report}");
+        reports.push(report);
+    }
+
+    if let [report] = reports.as_slice() {
+        for (path, content) in [
+            (&cli.json, Report::to_json as fn(&Report) -> _),
+            (&cli.csv, Report::to_csv),
+            (&cli.summary_csv, Report::summary_to_csv),
+        ] {
+            if let Some(path) = path {
+                std::fs::write(path, content(report)?)?;
+                info!("wrote the report to {}", path.display());
+            }
+        }
+        if let Some(name) = &cli.save_baseline {
+            std::fs::create_dir_all(&cli.baseline_dir)?;
+            let path = baseline_path(&cli.baseline_dir, name);
+            Baseline::of(name, report).save(&path)?;
+            info!("saved the baseline '{name}' to {}", path.display());
+        }
+        if let Some(name) = &cli.baseline {
+            let tolerance = Tolerance {
+                avg_steps: cli.tolerance_steps,
+                win_ratio: cli.tolerance_win / 100.0,
+            };
+            let check =
+                Baseline::load(baseline_path(&cli.baseline_dir, name))?.check(report, &tolerance);
+            println!("{check}");
+            check.into_result()?;
+        }
+        return Ok(());
+    }
+
+    let comparison = Comparison::new(reports)?;
+    println!("{comparison}");
+    for (path, content) in [
+        (&cli.json, Comparison::to_json as fn(&Comparison) -> _),
+        (&cli.csv, Comparison::to_csv),
+        (&cli.summary_csv, Comparison::summaries_to_csv),
+    ] {
+        if let Some(path) = path {
+            std::fs::write(path, content(&comparison)?)?;
+            info!("wrote the comparison to {}", path.display());
         }
     }
 
     Ok(())
 }
+
+/// the file the baseline `name` is stored in
+fn baseline_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.json"))
+}
+
+/// A builtin solver and it's configuration
+///
+/// The format is the name of the solver, optionally followed by a colon and a comma separated
+/// list of options. Only the decision tree solver has options: the objective (`expected` or
+/// `worst-case`) and the breadth (`breadth=N` or `breadth=all`), for example
+/// `tree:worst-case,breadth=4`. The breadth is `all` by default, which is only feasible for small
+/// wordlists, the bundled ones need a breadth like 8.
+#[derive(Debug, Clone, Default)]
+struct SolverSpec {
+    name: BuiltinSolverNames,
+    objective: Option<Objective>,
+    breadth: Option<Option<usize>>,
+}
+
+impl SolverSpec {
+    fn to_solver<'wl>(
+        &self,
+        wl: &'wl BuiltinWList,
+        cli: &Cli,
+    ) -> AnyBuiltinSolver<'wl, BuiltinWList> {
+        let solver = self.name.to_solver(wl);
+        let AnyBuiltinSolver::DecisionTree(tree_solver) = &solver else {
+            return solver;
+        };
+        let mut tree_solver = tree_solver.clone().hard_mode(cli.hard);
+        if let Some(objective) = self.objective {
+            tree_solver = tree_solver.objective(objective);
+        }
+        if let Some(breadth) = self.breadth {
+            tree_solver = tree_solver.breadth(breadth);
+        }
+        if let Some(dir) = &cli.tree_cache {
+            tree_solver = tree_solver.cache_dir(dir);
+        }
+        tree_solver.into()
+    }
+}
+
+impl FromStr for SolverSpec {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, options) = match s.split_once(':') {
+            Some((name, options)) => (name, Some(options)),
+            None => (s, None),
+        };
+        let mut spec = Self {
+            name: name.parse().map_err(|e| format!("{e}: {name}"))?,
+            ..Default::default()
+        };
+        let Some(options) = options else {
+            return Ok(spec);
+        };
+        if spec.name != BuiltinSolverNames::DecisionTree {
+            return Err(format!("the {} solver has no options", spec.name));
+        }
+        for option in options.split(',') {
+            match option {
+                "expected" => spec.objective = Some(Objective::Expected),
+                "worst-case" => spec.objective = Some(Objective::WorstCase),
+                "breadth=all" => spec.breadth = Some(None),
+                _ => match option.strip_prefix("breadth=").map(str::parse) {
+                    Some(Ok(breadth)) => spec.breadth = Some(Some(breadth)),
+                    _ => return Err(format!("unknown option for the tree solver: {option}")),
+                },
+            }
+        }
+        Ok(spec)
+    }
+}
+
+fn alphabet(cli: &Cli) -> Alphabet {
+    if cli.transliterate {
+        Alphabet::Latin
+    } else {
+        Alphabet::Native
+    }
+}
@@ -2,14 +2,16 @@
 // #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 use libpt::log::*;
 use wordle_analyzer::error::GameError;
+use wordle_analyzer::game::record::GameRecord;
 use wordle_analyzer::game::response::GuessResponse;
 
 use wordle_analyzer::wlist::builtin::BuiltinWList;
-use wordle_analyzer::wlist::word::Word;
+use wordle_analyzer::wlist::word::{Alphabet, Word};
 use wordle_analyzer::{self, game};
 
 #[derive(Parser, Clone, Debug)]
@@ -18,21 +20,50 @@ struct Cli {
     /// precompute all possibilities for better performance at runtime
     #[arg(short, long)]
     precompute: bool,
-    /// how long should the word be?
+    /// directory to store the precomputed possibilities in, so they only need to be computed once
+    ///
+    /// Only used with --precompute.
+    #[arg(long)]
+    precompute_cache: Option<std::path::PathBuf>,
+    /// how long should the word be? From 3 to 12 letters
+    ///
+    /// A replayed game always has the length of its record.
     #[arg(short, long, default_value_t = wordle_analyzer::DEFAULT_WORD_LENGTH)]
     length: usize,
     /// how many times can we guess?
     #[arg(short, long, default_value_t = wordle_analyzer::DEFAULT_MAX_STEPS)]
     max_steps: usize,
+    /// play in hard mode: every guess must use all hints revealed so far
+    #[arg(long)]
+    hard: bool,
+    /// play against an adversary that avoids the solution as long as possible
+    #[arg(long)]
+    adversarial: bool,
     /// more verbose logs
     #[arg(short, long)]
     verbose: bool,
     /// select a wordlist
     ///
     /// 'ger' and 'eng' are special values bundled with this executable, if the value does not
-    /// match either of those, it will be assumed to be a file path.
+    /// match either of those, it will be assumed to be a file path. The format of the file is
+    /// detected, it can be JSON, a plain list of words, or a TSV or CSV table of words and their
+    /// counts. Prefix the path with the format to choose it, like 'ranked:words.txt' for a list
+    /// with the most common words first. Give two files separated by a comma to load the
+    /// words that can be guessed and the solutions separately.
     #[arg(short, long, default_value_t = String::from("eng"))]
     wordlist: String,
+    /// write the words with the letters a to z only, like 'ae' for 'ä' and 'ss' for 'ß'
+    #[arg(long)]
+    transliterate: bool,
+    /// save a record of the game to this file when it is finished
+    ///
+    /// The record is written as JSON if the file name ends with '.json', otherwise in the compact
+    /// text form.
+    #[arg(long)]
+    record: Option<PathBuf>,
+    /// replay a record of a game step by step instead of playing
+    #[arg(long)]
+    replay: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -44,15 +75,17 @@ fn main() -> anyhow::Result<()> {
     }
     debug!("dumping CLI: {:#?}", cli);
 
-    let wl = match cli.wordlist.as_str() {
-        "ger" => BuiltinWList::german(cli.length),
-        "eng" => BuiltinWList::english(cli.length),
-        _ => BuiltinWList::load(&cli.wordlist, cli.length)?,
-    };
+    if let Some(path) = &cli.replay {
+        return replay(&cli, path);
+    }
+    let wl = BuiltinWList::select_with(&cli.wordlist, cli.length, alphabet(&cli))?;
     let builder = game::Game::builder(&wl)
         .length(cli.length)
         .max_steps(cli.max_steps)
-        .precompute(cli.precompute);
+        .hard_mode(cli.hard)
+        .adversarial(cli.adversarial)
+        .precompute(cli.precompute)
+        .precompute_cache(cli.precompute_cache.as_ref());
     let mut game = builder.build()?;
 
     debug!("{game:#?}");
@@ -60,7 +93,13 @@ fn main() -> anyhow::Result<()> {
     let mut response: GuessResponse;
     let mut guess: Word;
     loop {
-        guess = get_word(&cli, game.step())?;
+        guess = match alphabet(&cli).word(&get_word(&cli, game.step())?) {
+            Ok(w) => w,
+            Err(err) => {
+                println!("bad word: {err}");
+                continue;
+            }
+        };
         response = match game.guess(&guess, None) {
             Ok(r) => r,
             Err(err) => match err {
@@ -72,6 +111,10 @@ fn main() -> anyhow::Result<()> {
                     println!("bad word: word \"{w}\" is not in the wordlist",);
                     continue;
                 }
+                GameError::HardModeViolation(msg) => {
+                    println!("hard mode: {msg}");
+                    continue;
+                }
                 _ => {
                     return Err(err.into());
                 }
@@ -79,22 +122,68 @@ fn main() -> anyhow::Result<()> {
         };
 
         println!("{response}");
+        println!("{}", game.keyboard());
 
         if response.finished() {
             break;
         }
     }
     if response.won() {
-        println!("You win! You took {} guesses.", game.step() - 1);
+        println!("You win! You took {} guesses.", game.step());
+    } else if game.solution().is_none() && game.adversarial() {
+        println!(
+            "You lose! There were still {} possible solutions.",
+            game.candidates().len()
+        );
     } else {
         println!("You lose! The solution was {:?}.", game.solution());
     }
+    println!("\n{}", game.share());
+
+    if let Some(path) = &cli.record {
+        let record = game.record();
+        if is_json(path) {
+            std::fs::write(path, record.to_json()?)?;
+        } else {
+            std::fs::write(path, format!("{record}\n"))?;
+        }
+        println!("saved a record of the game to {}", path.display());
+    }
 
     Ok(())
 }
 
-fn get_word(_cli: &Cli, step: usize) -> std::io::Result<Word> {
-    let mut word = Word::new();
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// replay the record at `path`, with the words of the wordlist that have the length of the record
+fn replay(cli: &Cli, path: &Path) -> anyhow::Result<()> {
+    let content = std::fs::read_to_string(path)?;
+    let record: GameRecord = if is_json(path) {
+        GameRecord::from_json(&content)?
+    } else {
+        content.parse()?
+    };
+    let wl = BuiltinWList::select_with(&cli.wordlist, record.length(), alphabet(cli))?;
+    let mut replay = record.replay(&wl)?;
+    for response in replay.by_ref() {
+        let response = response?;
+        println!("{}. guess: {response}", response.step());
+    }
+    let game = replay.into_game();
+    if game.won() {
+        println!("The game was won with {} guesses.", game.responses().len());
+    } else if game.finished() {
+        println!("The game was lost, the solution was {:?}.", game.solution());
+    } else {
+        println!("The game was not finished.");
+    }
+    Ok(())
+}
+
+fn get_word(_cli: &Cli, step: usize) -> std::io::Result<String> {
+    let mut word = String::new();
     let stdin = std::io::stdin();
     let mut stdout = std::io::stdout();
 
@@ -105,3 +194,11 @@ fn get_word(_cli: &Cli, step: usize) -> std::io::Result<Word> {
 
     Ok(word)
 }
+
+fn alphabet(cli: &Cli) -> Alphabet {
+    if cli.transliterate {
+        Alphabet::Latin
+    } else {
+        Alphabet::Native
+    }
+}
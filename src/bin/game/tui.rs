@@ -1,5 +1,474 @@
 #![warn(clippy::all)]
+// #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
-fn main() {
-    unimplemented!();
+
+use clap::Parser;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Padding, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use wordle_analyzer::error::GameError;
+use wordle_analyzer::game::keyboard::{LetterState, KEYBOARD_ROWS};
+use wordle_analyzer::game::response::Status;
+use wordle_analyzer::game::{Game, GameBuilder};
+use wordle_analyzer::wlist::builtin::BuiltinWList;
+use wordle_analyzer::wlist::word::{transliterate, Alphabet, Word};
+use wordle_analyzer::wlist::WordList;
+
+const TILE_WIDTH: u16 = 5;
+const TILE_HEIGHT: u16 = 3;
+
+#[derive(Parser, Clone, Debug)]
+#[command(version, about, long_about, author)]
+struct Cli {
+    /// precompute all possibilities for better performance at runtime
+    #[arg(short, long)]
+    precompute: bool,
+    /// directory to store the precomputed possibilities in, so they only need to be computed once
+    ///
+    /// Only used with --precompute.
+    #[arg(long)]
+    precompute_cache: Option<std::path::PathBuf>,
+    /// how long should the word be? From 3 to 12 letters
+    #[arg(short, long, default_value_t = wordle_analyzer::DEFAULT_WORD_LENGTH)]
+    length: usize,
+    /// how many times can we guess?
+    #[arg(short, long, default_value_t = wordle_analyzer::DEFAULT_MAX_STEPS)]
+    max_steps: usize,
+    /// play in hard mode: every guess must use all hints revealed so far
+    #[arg(long)]
+    hard: bool,
+    /// play against an adversary that avoids the solution as long as possible
+    #[arg(long)]
+    adversarial: bool,
+    /// select a wordlist
+    ///
+    /// 'ger' and 'eng' are special values bundled with this executable, if the value does not
+    /// match either of those, it will be assumed to be a file path. The format of the file is
+    /// detected, it can be JSON, a plain list of words, or a TSV or CSV table of words and their
+    /// counts. Prefix the path with the format to choose it, like 'ranked:words.txt' for a list
+    /// with the most common words first. Give two files separated by a comma to load the
+    /// words that can be guessed and the solutions separately.
+    #[arg(short, long, default_value_t = String::from("eng"))]
+    wordlist: String,
+    /// write the words with the letters a to z only, like 'ae' for 'ä' and 'ss' for 'ß'
+    #[arg(long)]
+    transliterate: bool,
+}
+
+/// Statistics over all games played since the program was started
+#[derive(Debug, Clone, Default)]
+struct Stats {
+    played: usize,
+    won: usize,
+    streak: usize,
+    best_streak: usize,
+    /// how many games were won with how many guesses, starting at one guess
+    distribution: Vec<usize>,
+}
+
+impl Stats {
+    fn new(max_steps: usize) -> Self {
+        Self {
+            distribution: vec![0; max_steps],
+            ..Default::default()
+        }
+    }
+
+    fn record(&mut self, game: &Game<BuiltinWList>) {
+        self.played += 1;
+        if game.won() {
+            self.won += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+            let guesses = game.responses().len();
+            if self.distribution.len() < guesses {
+                self.distribution.resize(guesses, 0);
+            }
+            self.distribution[guesses - 1] += 1;
+        } else {
+            self.streak = 0;
+        }
+    }
+
+    fn win_ratio(&self) -> f64 {
+        if self.played == 0 {
+            0.0
+        } else {
+            self.won as f64 / self.played as f64
+        }
+    }
+}
+
+#[derive(Debug)]
+struct App<'wl> {
+    builder: GameBuilder<'wl, BuiltinWList>,
+    game: Game<'wl, BuiltinWList>,
+    keyboard: Vec<Vec<char>>,
+    /// the alphabet the typed letters are read in
+    alphabet: Alphabet,
+    input: String,
+    /// the message below the grid and if it is an error
+    message: Option<(String, bool)>,
+    stats: Stats,
+    quit: bool,
+}
+
+impl<'wl> App<'wl> {
+    fn new(builder: GameBuilder<'wl, BuiltinWList>, alphabet: Alphabet) -> anyhow::Result<Self> {
+        let game = builder.build()?;
+        Ok(Self {
+            keyboard: keyboard_for(game.wordlist()),
+            alphabet,
+            stats: Stats::new(game.max_steps()),
+            game,
+            builder,
+            input: String::new(),
+            message: None,
+            quit: false,
+        })
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                    self.quit = true;
+                } else if self.game.finished() {
+                    self.handle_finished_key(key.code)?;
+                } else {
+                    self.handle_key(key.code);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Enter => self.submit(),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) if c.is_alphabetic() => {
+                let letters = match self.alphabet {
+                    Alphabet::Native => c.to_lowercase().collect(),
+                    Alphabet::Latin => transliterate(&c.to_lowercase().to_string()),
+                };
+                if self.input.chars().count() + letters.chars().count() <= self.game.length() {
+                    self.input.push_str(&letters);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn handle_finished_key(&mut self, code: KeyCode) -> anyhow::Result<()> {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.quit = true,
+            KeyCode::Enter | KeyCode::Char('n') => {
+                self.game = self.builder.build()?;
+                self.message = None;
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn submit(&mut self) {
+        let guess: Word = match self.alphabet.word(&self.input) {
+            Ok(w) => w,
+            Err(err) => {
+                self.message = Some((err.to_string(), true));
+                return;
+            }
+        };
+        match self.game.guess(&guess, None) {
+            Ok(_) => {
+                self.input.clear();
+                self.message = None;
+                if self.game.finished() {
+                    self.stats.record(&self.game);
+                }
+            }
+            Err(err) => {
+                let msg = match err {
+                    GameError::GuessHasWrongLength(_) => {
+                        format!("The word must have {} letters", self.game.length())
+                    }
+                    GameError::WordNotInWordlist(w) => format!("\"{w}\" is not in the wordlist"),
+                    GameError::HardModeViolation(msg) => format!("Hard mode: {msg}"),
+                    other => other.to_string(),
+                };
+                self.message = Some((msg, true));
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let grid_height = self.game.max_steps() as u16 * TILE_HEIGHT;
+        let [title, grid, message, keyboard, help] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(grid_height),
+            Constraint::Length(2),
+            Constraint::Length(self.keyboard.len() as u16),
+            Constraint::Length(2),
+        ])
+        .flex(Flex::Center)
+        .areas(frame.area());
+
+        let mut mode = Vec::new();
+        if self.game.hard_mode() {
+            mode.push("hard mode");
+        }
+        if self.game.adversarial() {
+            mode.push("adversarial");
+        }
+        let mut title_line = Line::from("Wordle".bold());
+        if !mode.is_empty() {
+            title_line.push_span(format!(" ({})", mode.join(", ")).dim());
+        }
+        frame.render_widget(title_line.centered(), title);
+
+        self.draw_grid(frame, grid);
+
+        if let Some((msg, is_error)) = &self.message {
+            let style = if *is_error {
+                Style::new().fg(Color::Red).bold()
+            } else {
+                Style::new()
+            };
+            frame.render_widget(Line::styled(msg.as_str(), style).centered(), message);
+        }
+
+        self.draw_keyboard(frame, keyboard);
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::default(),
+                Line::from("enter: guess  backspace: delete  esc: quit".dim()),
+            ])
+            .alignment(Alignment::Center),
+            help,
+        );
+
+        if self.game.finished() {
+            self.draw_result(frame);
+        }
+    }
+
+    fn draw_grid(&self, frame: &mut Frame, area: Rect) {
+        let rows = Layout::vertical(vec![Constraint::Length(TILE_HEIGHT); self.game.max_steps()])
+            .split(area);
+        for (idx, row) in rows.iter().enumerate() {
+            let tiles =
+                Layout::horizontal(vec![Constraint::Length(TILE_WIDTH); self.game.length()])
+                    .flex(Flex::Center)
+                    .split(*row);
+            let letters: Vec<(char, Option<Status>)> = match self.game.responses().get(idx) {
+                Some(response) => response
+                    .evaluation()
+                    .units()
+                    .map(|(c, s)| (c, Some(s)))
+                    .collect(),
+                None if idx == self.game.responses().len() => {
+                    self.input.chars().map(|c| (c, None)).collect()
+                }
+                None => Vec::new(),
+            };
+            for (i, tile) in tiles.iter().enumerate() {
+                let (letter, status) = match letters.get(i) {
+                    Some((c, s)) => (c.to_uppercase().to_string(), *s),
+                    None => (String::new(), None),
+                };
+                let (border, style) = match status {
+                    Some(status) => (tile_style(status), tile_style(status).bold()),
+                    None if !letter.is_empty() => {
+                        (Style::new().fg(Color::White), Style::new().bold())
+                    }
+                    None => (Style::new().fg(Color::DarkGray), Style::new()),
+                };
+                frame.render_widget(
+                    Paragraph::new(letter)
+                        .alignment(Alignment::Center)
+                        .style(style)
+                        .block(Block::bordered().border_style(border)),
+                    *tile,
+                );
+            }
+        }
+    }
+
+    fn draw_keyboard(&self, frame: &mut Frame, area: Rect) {
+        let rows = Layout::vertical(vec![Constraint::Length(1); self.keyboard.len()]).split(area);
+        let knowledge = self.game.keyboard();
+        for (keys, row) in self.keyboard.iter().zip(rows.iter()) {
+            let mut line = Line::default();
+            for key in keys {
+                line.push_span(Span::styled(
+                    format!(" {} ", key.to_uppercase()),
+                    key_style(knowledge.state(*key)),
+                ));
+                line.push_span(" ");
+            }
+            frame.render_widget(line.centered(), *row);
+        }
+    }
+
+    fn draw_result(&self, frame: &mut Frame) {
+        let mut lines = Vec::new();
+        if self.game.won() {
+            lines.push(Line::from("You win!".green().bold()));
+            lines.push(Line::from(format!(
+                "You took {} guesses.",
+                self.game.responses().len()
+            )));
+        } else {
+            lines.push(Line::from("You lose!".red().bold()));
+            match self.game.solution() {
+                Some(solution) => lines.push(Line::from(vec![
+                    Span::from("The solution was "),
+                    Span::from(solution.0.to_uppercase()).bold(),
+                    Span::from("."),
+                ])),
+                None => lines.push(Line::from(format!(
+                    "There were still {} possible solutions.",
+                    self.game.candidates().len()
+                ))),
+            }
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(format!(
+            "played: {}  won: {:.0}%  streak: {}  best streak: {}",
+            self.stats.played,
+            self.stats.win_ratio() * 100.0,
+            self.stats.streak,
+            self.stats.best_streak
+        )));
+        lines.push(Line::default());
+        let most = self
+            .stats
+            .distribution
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .max(1);
+        for (idx, n) in self.stats.distribution.iter().enumerate() {
+            let bar = "█".repeat(1 + n * 20 / most);
+            let mut style = Style::new().fg(Color::DarkGray);
+            if self.game.won() && idx + 1 == self.game.responses().len() {
+                style = Style::new().fg(Color::Green);
+            }
+            lines.push(
+                Line::from(vec![
+                    Span::from(format!("{} ", idx + 1)),
+                    Span::styled(bar, style),
+                    Span::from(format!(" {n}")),
+                ])
+                .left_aligned(),
+            );
+        }
+        lines.push(Line::default());
+        lines.push(Line::from("enter: new game  q: quit".dim()));
+
+        let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4;
+        let height = lines.len() as u16 + 2;
+        let area = centered(frame.area(), width, height);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center).block(
+                Block::bordered()
+                    .title(" Statistics ")
+                    .padding(Padding::horizontal(1)),
+            ),
+            area,
+        );
+    }
+}
+
+/// how a tile of the grid looks with the [Status] of it's letter
+fn tile_style(status: Status) -> Style {
+    match status {
+        Status::Matched => Style::new().fg(Color::Black).bg(Color::Green),
+        Status::Exists => Style::new().fg(Color::Black).bg(Color::Yellow),
+        Status::None => Style::new().fg(Color::White).bg(Color::DarkGray),
+    }
+}
+
+/// how a key of the on screen keyboard looks with what is known about it's letter
+fn key_style(state: LetterState) -> Style {
+    match state {
+        LetterState::Absent => Style::new()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::CROSSED_OUT),
+        LetterState::Present => tile_style(Status::Exists),
+        LetterState::Confirmed => tile_style(Status::Matched),
+        LetterState::Unknown => Style::new().fg(Color::Black).bg(Color::Gray),
+    }
+}
+
+/// The rows of the on screen keyboard for a wordlist
+///
+/// Letters that occur in the wordlist but are not part of the [KEYBOARD_ROWS], like umlauts, are put
+/// into an extra row.
+fn keyboard_for(wl: &impl WordList) -> Vec<Vec<char>> {
+    let mut rows: Vec<Vec<char>> = KEYBOARD_ROWS.iter().map(|r| r.chars().collect()).collect();
+    let mut extra: Vec<char> = wl
+        .wordmap()
+        .keys()
+        .flat_map(|w| w.chars())
+        .filter(|c| c.is_alphabetic() && !KEYBOARD_ROWS.iter().any(|r| r.contains(*c)))
+        .collect();
+    extra.sort();
+    extra.dedup();
+    if !extra.is_empty() {
+        rows.push(extra);
+    }
+    rows
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let wl = BuiltinWList::select_with(&cli.wordlist, cli.length, alphabet(&cli))?;
+    let builder = Game::builder(&wl)
+        .length(cli.length)
+        .max_steps(cli.max_steps)
+        .hard_mode(cli.hard)
+        .adversarial(cli.adversarial)
+        .precompute(cli.precompute)
+        .precompute_cache(cli.precompute_cache.as_ref());
+    let mut app = App::new(builder, alphabet(&cli))?;
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+fn alphabet(cli: &Cli) -> Alphabet {
+    if cli.transliterate {
+        Alphabet::Latin
+    } else {
+        Alphabet::Native
+    }
 }
@@ -11,10 +11,12 @@ use strum::EnumIter;
 use wordle_analyzer::error::Error;
 use wordle_analyzer::game::evaluation::Evaluation;
 use wordle_analyzer::game::response::GuessResponse;
+use wordle_analyzer::game::share::ShareGrid;
 
-use wordle_analyzer::solve::{BuiltinSolverNames, Solver};
+use wordle_analyzer::solve::analysis::Analysis;
+use wordle_analyzer::solve::{AnyBuiltinSolver, BuiltinSolverNames, Session};
 use wordle_analyzer::wlist::builtin::BuiltinWList;
-use wordle_analyzer::wlist::word::Word;
+use wordle_analyzer::wlist::word::{Alphabet, Word};
 use wordle_analyzer::wlist::WordList;
 use wordle_analyzer::{self, game};
 
@@ -24,12 +26,20 @@ struct Cli {
     /// precompute all possibilities for better performance at runtime
     #[arg(short, long)]
     precompute: bool,
-    /// how long should the word be?
+    /// directory to store the precomputed possibilities in, so they only need to be computed once
+    ///
+    /// Only used with --precompute.
+    #[arg(long)]
+    precompute_cache: Option<std::path::PathBuf>,
+    /// how long should the word be? From 3 to 12 letters
     #[arg(short, long, default_value_t = wordle_analyzer::DEFAULT_WORD_LENGTH)]
     length: usize,
     /// how many times can we guess?
     #[arg(short, long, default_value_t = wordle_analyzer::DEFAULT_MAX_STEPS)]
     max_steps: usize,
+    /// play in hard mode: every guess must use all hints revealed so far
+    #[arg(long)]
+    hard: bool,
     /// more verbose logs
     #[command(flatten)]
     verbose: libpt::cli::args::VerbosityLevel,
@@ -49,14 +59,33 @@ struct Cli {
     /// selected solver behaves when trying to guess a specific solution, which can help reproduce
     /// behavior.
     #[arg(short, long)]
-    solution: Option<Word>,
+    solution: Option<String>,
 
     /// select a wordlist
     ///
     /// 'ger' and 'eng' are special values bundled with this executable, if the value does not
-    /// match either of those, it will be assumed to be a file path.
+    /// match either of those, it will be assumed to be a file path. The format of the file is
+    /// detected, it can be JSON, a plain list of words, or a TSV or CSV table of words and their
+    /// counts. Prefix the path with the format to choose it, like 'ranked:words.txt' for a list
+    /// with the most common words first. Give two files separated by a comma to load the
+    /// words that can be guessed and the solutions separately.
     #[arg(short, long, default_value_t = String::from("eng"))]
     wordlist: String,
+    /// write the words with the letters a to z only, like 'ae' for 'ä' and 'ss' for 'ß'
+    #[arg(long)]
+    transliterate: bool,
+
+    /// directory to store decision trees in, so they only need to be built once
+    ///
+    /// Only used with the decisiontree solver.
+    #[arg(long)]
+    tree_cache: Option<std::path::PathBuf>,
+    /// how many guesses the decisiontree solver tries for each node, all if not given
+    ///
+    /// Trying all guesses gives the best tree, but is only feasible for small wordlists. For the
+    /// bundled wordlists, use a breadth like 8.
+    #[arg(long)]
+    tree_breadth: Option<usize>,
 }
 
 #[derive(Subcommand, Debug, EnumIter, Clone)]
@@ -85,6 +114,26 @@ enum ReplCommand {
     Solve,
     /// Show the current state of the game
     Show,
+    /// Show the share grid of the current game
+    Share,
+    /// Review each guess of the current game, compared with the guesses of the solver
+    Analyze,
+    /// Continue with a game from a share grid, like the ones posted for the daily wordle
+    ///
+    /// As the grid does not show the letters, the most likely guesses that give the grid for the
+    /// solution are used.
+    Import {
+        /// file with the share grid
+        file: std::path::PathBuf,
+        /// the solution of the shared game
+        solution: String,
+    },
+    /// Show the words that might still be the solution
+    Candidates {
+        /// how many words to show at most
+        #[arg(default_value_t = 20)]
+        amount: usize,
+    },
     /// Display data about the wordlist
     Wl {
         #[command(subcommand)]
@@ -123,17 +172,15 @@ fn main() -> anyhow::Result<()> {
 }
 
 fn help_guess_interactive(cli: Cli) -> anyhow::Result<()> {
-    let wl = match cli.wordlist.as_str() {
-        "ger" => BuiltinWList::german(cli.length),
-        "eng" => BuiltinWList::english(cli.length),
-        _ => BuiltinWList::load(&cli.wordlist, cli.length)?,
-    };
+    let wl = BuiltinWList::select_with(&cli.wordlist, cli.length, alphabet(&cli))?;
     let builder = game::GameBuilder::new(&wl, false)
         .length(cli.length)
         .max_steps(cli.max_steps)
-        .precompute(cli.precompute);
-    let solver = cli.solver.to_solver(&wl);
-    let mut game = builder.build()?;
+        .hard_mode(cli.hard)
+        .precompute(cli.precompute)
+        .precompute_cache(cli.precompute_cache.as_ref());
+    let solver = solver_for(&cli, &wl);
+    let mut session = Session::new(&solver, builder.build()?);
 
     let mut repl = libpt::cli::repl::DefaultRepl::<ReplCommand>::default();
 
@@ -164,39 +211,108 @@ fn help_guess_interactive(cli: Cli) -> anyhow::Result<()> {
             ReplCommand::Exit => break,
             ReplCommand::Wl { cmd } => wlcommand_handler(&cli, &cmd, &wl)?,
             ReplCommand::Show => {
-                println!("{}", game);
+                println!("{}", session.game());
             }
-            ReplCommand::Solve => {
-                let best_guess = solver.guess_for(&game);
-                if best_guess.is_err() {
-                    eprintln!("{}", style(best_guess.unwrap_err()).red().bold());
-                    continue;
+            ReplCommand::Analyze => match Analysis::of(session.solver(), session.game()) {
+                Ok(analysis) => println!("{analysis}"),
+                Err(e) => eprintln!("{}", style(e).red().bold()),
+            },
+            ReplCommand::Share => {
+                println!("{}", session.game().share());
+            }
+            ReplCommand::Import { file, solution } => {
+                let game = match alphabet(&cli)
+                    .word(&solution)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|solution| import(&file, &solution, &wl))
+                {
+                    Ok(g) => g,
+                    Err(e) => {
+                        eprintln!("{}", style(e).red().bold());
+                        continue;
+                    }
+                };
+                session.restart(game);
+                println!("{}", session.game());
+            }
+            ReplCommand::Candidates { amount } => {
+                let candidates = session.candidates();
+                println!("{} candidates", candidates.len());
+                for c in candidates.iter().take(amount) {
+                    println!("\t\"{}\":\t{:.08}%", c.0, c.1 * 100.0);
                 }
-                trace!("game state: {game:?}");
-                println!("best guess: {}", best_guess.unwrap());
+            }
+            ReplCommand::Solve => {
+                let best_guess = match session.suggest() {
+                    Ok(g) => g,
+                    Err(e) => {
+                        eprintln!("{}", style(e).red().bold());
+                        continue;
+                    }
+                };
+                trace!("game state: {:?}", session.game());
+                println!("best guess: {best_guess}");
             }
             ReplCommand::Guess {
                 your_guess,
                 evalutation,
             } => {
-                let evaluation_converted: Evaluation =
-                    Evaluation::build(&your_guess, &evalutation)?;
-                let guess = game.guess(&your_guess, Some(evaluation_converted));
+                let your_guess = match alphabet(&cli).word(&your_guess) {
+                    Ok(w) => w,
+                    Err(e) => {
+                        eprintln!("{}", style(e).red().bold());
+                        continue;
+                    }
+                };
+                let evaluation_converted = match Evaluation::build(&your_guess, &evalutation) {
+                    Ok(e) => e,
+                    Err(e) => {
+                        eprintln!("{}", style(e).red().bold());
+                        continue;
+                    }
+                };
+                let guess = session.guess(&your_guess, Some(evaluation_converted));
                 debug!("your guess: {guess:?}");
-                if guess.is_err() {
-                    eprintln!("{}", style(guess.unwrap_err()).red().bold());
-                    continue;
+                match guess {
+                    Ok(g) => println!("{g}"),
+                    Err(e) => {
+                        eprintln!("{}", style(e).red().bold());
+                        continue;
+                    }
                 }
-                println!("{}", guess.unwrap());
-                trace!("game state: {game:#?}");
+                trace!("game state: {:#?}", session.game());
             }
-            ReplCommand::New => game = builder.build()?,
-            ReplCommand::Undo { n } => game.undo(n)?,
+            ReplCommand::New => session.restart(builder.build()?),
+            ReplCommand::Undo { n } => session.undo(n)?,
         }
     }
     Ok(())
 }
 
+fn import<'wl>(
+    path: &std::path::Path,
+    solution: &Word,
+    wl: &'wl BuiltinWList,
+) -> anyhow::Result<game::Game<'wl, BuiltinWList>> {
+    let grid: ShareGrid = std::fs::read_to_string(path)?.parse()?;
+    for (idx, words) in grid.candidates(wl, solution).iter().enumerate() {
+        debug!("{} words fit row {}", words.len(), idx + 1);
+    }
+    Ok(grid.to_game(wl, solution)?)
+}
+
+fn solver_for<'wl>(cli: &Cli, wl: &'wl BuiltinWList) -> AnyBuiltinSolver<'wl, BuiltinWList> {
+    let solver = cli.solver.to_solver(wl);
+    let AnyBuiltinSolver::DecisionTree(tree_solver) = solver else {
+        return solver;
+    };
+    let tree_solver = tree_solver.hard_mode(cli.hard).breadth(cli.tree_breadth);
+    match &cli.tree_cache {
+        Some(dir) => tree_solver.cache_dir(dir).into(),
+        None => tree_solver.into(),
+    }
+}
+
 fn wlcommand_handler(_cli: &Cli, cmd: &WlCommand, wl: &impl WordList) -> anyhow::Result<()> {
     match cmd {
         WlCommand::Stats => {
@@ -205,7 +321,12 @@ fn wlcommand_handler(_cli: &Cli, cmd: &WlCommand, wl: &impl WordList) -> anyhow:
         WlCommand::Top { amount } => {
             println!();
             for s in wl.n_most_likely(*amount).iter() {
-                println!("\t\"{}\":\t{:.08}%", s.0, s.1 * 100.0);
+                println!(
+                    "\t\"{}\":\t{:.08}%\tprior: {:.4}",
+                    s.0,
+                    s.1 * 100.0,
+                    wl.prior(&s.0)
+                );
             }
         }
     }
@@ -213,18 +334,16 @@ fn wlcommand_handler(_cli: &Cli, cmd: &WlCommand, wl: &impl WordList) -> anyhow:
 }
 
 fn play_native_non_interactive(cli: Cli) -> anyhow::Result<()> {
-    let wl = match cli.wordlist.as_str() {
-        "ger" => BuiltinWList::german(cli.length),
-        "eng" => BuiltinWList::english(cli.length),
-        _ => BuiltinWList::load(&cli.wordlist, cli.length)?,
-    };
+    let wl = BuiltinWList::select_with(&cli.wordlist, cli.length, alphabet(&cli))?;
     trace!("wordlist: {wl}");
     let mut builder = game::Game::builder(&wl)
         .length(cli.length)
         .max_steps(cli.max_steps)
-        .precompute(cli.precompute);
-    if cli.solution.is_some() {
-        let solw: Word = cli.solution.unwrap();
+        .hard_mode(cli.hard)
+        .precompute(cli.precompute)
+        .precompute_cache(cli.precompute_cache.as_ref());
+    if let Some(solw) = &cli.solution {
+        let solw = alphabet(&cli).word(solw)?;
         let sol = wl.get_word(&solw);
         if sol.is_none() {
             eprintln!("the requested solution \"{solw}\" is not in the wordlist");
@@ -235,26 +354,36 @@ fn play_native_non_interactive(cli: Cli) -> anyhow::Result<()> {
         }
         builder = builder.solution(sol);
     }
-    let solver = cli.solver.to_solver(&wl);
-    let mut game = builder.build()?;
+    let solver = solver_for(&cli, &wl);
+    let mut session = Session::new(&solver, builder.build()?);
 
-    trace!("{game:#?}");
+    trace!("{:#?}", session.game());
 
     let mut response: GuessResponse;
-    let mut _guess: Word;
     loop {
-        response = solver.make_a_move(&mut game)?;
-        trace!("game state: {game:#?}");
-        println!("{}. guess: {response}", game.step() - 1);
+        response = session.make_a_move()?;
+        trace!("game state: {:#?}", session.game());
+        println!("{}. guess: {response}", session.game().step());
 
         if response.finished() {
             break;
         }
     }
     if response.won() {
-        println!("You win! You took {} guesses.", game.step() - 1);
+        println!("You win! You took {} guesses.", session.game().step());
     } else {
-        println!("You lose! The solution was {:?}.", game.solution());
+        println!(
+            "You lose! The solution was {:?}.",
+            session.game().solution()
+        );
     }
     Ok(())
 }
+
+fn alphabet(cli: &Cli) -> Alphabet {
+    if cli.transliterate {
+        Alphabet::Latin
+    } else {
+        Alphabet::Native
+    }
+}
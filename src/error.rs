@@ -36,7 +36,28 @@ pub enum Error {
     #[error("Error sharing the benchmark data over multiple threads")]
     Mutex {
         #[from]
-        source: std::sync::PoisonError<Report>,
+        source: Box<std::sync::PoisonError<Report>>,
+    },
+    #[error("I/O error (probably filesystem related)")]
+    IO {
+        #[from]
+        source: std::io::Error,
+    },
+    #[error("Could not (de)serialize data")]
+    Serde {
+        #[from]
+        source: serde_json::Error,
+    },
+    #[error("Could not start the threads of the benchmark")]
+    ThreadPool {
+        #[from]
+        source: rayon::ThreadPoolBuildError,
+    },
+    #[cfg(feature = "serde")]
+    #[error("Could not write CSV data")]
+    Csv {
+        #[from]
+        source: csv::Error,
     },
 }
 
@@ -52,12 +73,38 @@ pub enum GameError {
     InvalidEvaluationSyntax(String),
     #[error("The length of guess and evaluation must be the same")]
     GuessAndEvalNotSameLen((String, String)),
+    #[error("Can not precompute patterns for words with {0} letters")]
+    CannotPrecompute(usize),
+    #[error("The guess does not use all revealed hints, as required in hard mode: {0}")]
+    HardModeViolation(String),
+    #[error(
+        "Games with {0} letters are not supported, they can have from {} to {} letters",
+        crate::MIN_WORD_LENGTH,
+        crate::MAX_WORD_LENGTH
+    )]
+    UnsupportedLength(usize),
+    #[error("The wordlist has no words with {0} letters")]
+    NoWordsWithLength(usize),
+    #[error("A game needs at least one board")]
+    NoBoards,
+    #[error("Invalid game record: {0}")]
+    BadRecord(String),
+    #[error("Invalid share grid: {0}")]
+    BadShareGrid(String),
+    #[error("No word of the wordlist fits row {0} of the share grid")]
+    NoFeasibleGuess(usize),
 }
 
 #[derive(Debug, Clone, Error)]
 pub enum BenchError {
     #[error("Trying to modify a finished report")]
     ModifyFinishedReport,
+    #[error("The reports can not be compared: {0}")]
+    NotComparable(String),
+    #[error("The benchmark regressed against the baseline '{0}': {1}")]
+    Regression(String, String),
+    #[error("The target '{0}' does not have {1} letters")]
+    TargetHasWrongLength(Word, usize),
 }
 
 #[derive(Debug, Clone, Error)]
@@ -66,6 +113,8 @@ pub enum SolverError {
     NoMatches(Option<WordData>),
     #[error("Unknown builtin solver")]
     UnknownBuiltinSolver,
+    #[error("The decision tree was made for another wordlist ({0})")]
+    TreeMismatch(String),
 }
 
 #[derive(Debug, Error)]
@@ -85,4 +134,18 @@ pub enum WordlistError {
         #[from]
         source: regex::Error,
     },
+    #[error("The word is too long to be stored ({0})")]
+    WordTooLong(String),
+    #[error("The solution is not in the words that can be guessed ({0})")]
+    UnknownSolution(Word),
+    #[error("Unknown wordlist format: {0}")]
+    UnknownFormat(String),
+    #[error("Bad wordlist line {0}: {1}")]
+    BadLine(usize, String),
+    #[cfg(feature = "serde")]
+    #[error("Could not read the wordlist table")]
+    Csv {
+        #[from]
+        source: csv::Error,
+    },
 }
@@ -0,0 +1,201 @@
+//! Everything the [responses](GuessResponse) of a game reveal about the solution
+
+use crate::wlist::word::{Word, WordData};
+
+use super::evaluation::Pattern;
+use super::response::{GuessResponse, Status};
+
+/// How often a letter may occur in the solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LetterBounds {
+    letter: char,
+    min: usize,
+    max: usize,
+}
+
+/// The knowledge about the solution that was gained from the responses to guesses
+///
+/// A word fits the constraints exactly if every guess would have gotten the same [Pattern] with
+/// that word as the solution. The constraints take repeated letters into account: If a letter
+/// occurs twice in a guess and only one of them is marked, the solution contains that letter
+/// exactly once.
+///
+/// The constraints can be built from all [responses](GuessResponse) at once with
+/// [from_responses](Constraints::from_responses), or updated after each guess with
+/// [add_response](Constraints::add_response). The [Game](super::Game) keeps it's constraints up
+/// to date, see [Game::constraints](super::Game::constraints).
+///
+/// ## Example
+///
+/// ```
+/// use wordle_analyzer::game::constraints::Constraints;
+/// use wordle_analyzer::game::evaluation::pattern;
+///
+/// let mut constraints = Constraints::new(5);
+/// constraints.add(&"eerie".parse().unwrap(), pattern("sheep", "eerie"));
+/// assert!(constraints.matches("sheep"));
+/// assert!(constraints.matches("cheek"));
+/// // two of the 'e' in eerie were marked, so the solution has two of them
+/// assert!(!constraints.matches("cheap"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Constraints {
+    length: usize,
+    /// the letter at each position, if it was matched
+    known: Vec<Option<char>>,
+    /// letters that are known not to be at each position
+    excluded: Vec<Vec<char>>,
+    /// how often the letters that were guessed may occur
+    bounds: Vec<LetterBounds>,
+}
+
+impl Constraints {
+    /// Constraints for words with `length` letters that don't know anything yet
+    pub fn new(length: usize) -> Self {
+        Self {
+            length,
+            known: vec![None; length],
+            excluded: vec![Vec::new(); length],
+            bounds: Vec::new(),
+        }
+    }
+
+    /// Build the constraints from all responses of a game
+    pub fn from_responses(length: usize, responses: &[GuessResponse]) -> Self {
+        let mut constraints = Self::new(length);
+        for response in responses {
+            constraints.add_response(response);
+        }
+        constraints
+    }
+
+    /// Add what a [GuessResponse] tells about the solution
+    pub fn add_response(&mut self, response: &GuessResponse) {
+        self.add(response.guess(), response.evaluation().pattern());
+    }
+
+    /// Add what the [Pattern] of `guess` tells about the solution
+    ///
+    /// The pattern should be one that [pattern](super::evaluation::pattern) can produce, that is
+    /// if a letter is marked as [existing](Status::Exists), no earlier occurrence of it in the
+    /// guess has [Status::None].
+    pub fn add(&mut self, guess: &Word, mut pattern: Pattern) {
+        // letters of the guess and how often they were marked
+        let mut marked: Vec<(char, usize, bool)> = Vec::new();
+        for (idx, c) in guess.chars().enumerate().take(self.length) {
+            let status = pattern % 3;
+            pattern /= 3;
+            if status == Status::Matched as Pattern {
+                self.known[idx] = Some(c);
+            } else if !self.excluded[idx].contains(&c) {
+                self.excluded[idx].push(c);
+            }
+            let entry = match marked.iter_mut().find(|(l, _, _)| *l == c) {
+                Some(entry) => entry,
+                None => {
+                    marked.push((c, 0, false));
+                    marked.last_mut().unwrap()
+                }
+            };
+            if status == Status::None as Pattern {
+                // the solution has no more of this letter than were marked
+                entry.2 = true;
+            } else {
+                entry.1 += 1;
+            }
+        }
+        for (letter, n, capped) in marked {
+            let max = if capped { n } else { self.length };
+            match self.bounds.iter_mut().find(|b| b.letter == letter) {
+                Some(bounds) => {
+                    bounds.min = bounds.min.max(n);
+                    bounds.max = bounds.max.min(max);
+                }
+                None => self.bounds.push(LetterBounds {
+                    letter,
+                    min: n,
+                    max,
+                }),
+            }
+        }
+    }
+
+    /// Check if `word` might be the solution
+    pub fn matches(&self, word: &str) -> bool {
+        let mut len = 0;
+        for (idx, c) in word.chars().enumerate() {
+            if idx >= self.length {
+                return false;
+            }
+            match self.known[idx] {
+                Some(k) if k != c => return false,
+                _ => (),
+            }
+            if self.excluded[idx].contains(&c) {
+                return false;
+            }
+            len += 1;
+        }
+        len == self.length
+            && self.bounds.iter().all(|b| {
+                let n = word.chars().filter(|c| *c == b.letter).count();
+                b.min <= n && n <= b.max
+            })
+    }
+
+    /// Keep only the words that might be the solution
+    pub fn filter<I: IntoIterator<Item = WordData>>(&self, words: I) -> Vec<WordData> {
+        words.into_iter().filter(|w| self.matches(&w.0)).collect()
+    }
+
+    /// Remove all candidates that can not be the solution
+    ///
+    /// After a new response was [added](Constraints::add_response), this narrows down the
+    /// candidates of the previous step without going over the whole wordlist again.
+    pub fn retain(&self, candidates: &mut Vec<WordData>) {
+        candidates.retain(|w| self.matches(&w.0));
+    }
+
+    /// the length of the words these constraints are for
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Check if nothing is known about the solution yet
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// the letter at each position, if it is known
+    pub fn known(&self) -> &[Option<char>] {
+        &self.known
+    }
+
+    /// the letters that are known not to be at position `idx`
+    pub fn excluded_at(&self, idx: usize) -> &[char] {
+        self.excluded
+            .get(idx)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// How often `letter` occurs at least and at most in the solution
+    ///
+    /// Returns [None] if the letter was not guessed yet.
+    pub fn occurrences(&self, letter: char) -> Option<(usize, usize)> {
+        self.bounds
+            .iter()
+            .find(|b| b.letter == letter)
+            .map(|b| (b.min, b.max))
+    }
+
+    /// Check if `letter` is known to be in the solution
+    pub fn contains(&self, letter: char) -> bool {
+        self.occurrences(letter).is_some_and(|(min, _)| min > 0)
+    }
+
+    /// Check if `letter` is known not to be in the solution
+    pub fn excludes(&self, letter: char) -> bool {
+        self.occurrences(letter).is_some_and(|(_, max)| max == 0)
+    }
+}
@@ -4,21 +4,35 @@ use crate::wlist::word::Word;
 
 use super::response::Status;
 use super::{GameError, WResult};
+use crate::error::WordlistError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// the [char] of the guess and the [Status] associated with it
 pub type EvaluationUnit = (char, Status);
 
-/// Basically a [String] with extra information associated with each char
-#[derive(Debug, Clone, PartialEq, Default)]
+/// Compact encoding of the [Statuses](Status) of an [Evaluation]
+///
+/// Each letter is one digit of a number in base 3, with the first letter being the least
+/// significant digit. The digit is the numeric value of the [Status], so a word where all letters
+/// are [matched](Status::Matched) has the pattern `3^len - 1`.
+pub type Pattern = u32;
+
+/// A guessed [Word] together with the [Status] of each of it's chars
+///
+/// This is stored as the [Word] and it's [Pattern], so it is [Copy] and does not allocate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Evaluation {
-    inner: Vec<EvaluationUnit>,
+    guess: Word,
+    pattern: Pattern,
 }
 
 impl Evaluation {
     /// Display the evaluation color coded
     pub fn colorized_display(&self) -> Vec<StyledObject<String>> {
         let mut buf = Vec::new();
-        for e in self.inner.iter() {
+        for e in self.units() {
             let mut c = style(e.0.to_string());
             if e.1 == Status::Matched {
                 c = c.green();
@@ -55,7 +69,7 @@ impl Evaluation {
     /// contains 'l' and 'e', but on another index.
     ///
     pub fn build(guess: &Word, eval_str: &str) -> WResult<Self> {
-        if guess.len() != eval_str.len() {
+        if guess.chars().count() != eval_str.chars().count() {
             return Err(GameError::GuessAndEvalNotSameLen((
                 guess.to_string(),
                 eval_str.to_string(),
@@ -66,45 +80,190 @@ impl Evaluation {
         for (c, e) in guess.chars().zip(eval_str.chars()) {
             v.push((c, Status::from(e)))
         }
-        Ok(v.into())
+        Ok(v.try_into()?)
     }
 
-    pub fn inner(&self) -> &Vec<EvaluationUnit> {
-        &self.inner
+    /// Write the [Statuses](Status) in the format that [build](Evaluation::build) reads
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use wordle_analyzer::game::evaluation::{pattern, Evaluation};
+    ///
+    /// let guess = "slate".parse().unwrap();
+    /// let evaluation = Evaluation::from_pattern(&guess, pattern("crane", "slate"));
+    /// assert_eq!(evaluation.to_eval_string(), "xxcxc");
+    /// ```
+    pub fn to_eval_string(&self) -> String {
+        self.units().map(|(_, status)| char::from(status)).collect()
     }
 
-    pub fn inner_mut(&mut self) -> &mut Vec<EvaluationUnit> {
-        &mut self.inner
+    /// Get the chars of the guess with their [Status]
+    pub fn units(&self) -> impl Iterator<Item = EvaluationUnit> + '_ {
+        let mut pattern = self.pattern;
+        self.guess.chars().map(move |c| {
+            let status = match pattern % 3 {
+                0 => Status::None,
+                1 => Status::Exists,
+                _ => Status::Matched,
+            };
+            pattern /= 3;
+            (c, status)
+        })
     }
 
     pub fn guess(&self) -> Word {
-        Word::from(self)
+        self.guess
+    }
+
+    /// Encode the [Statuses](Status) of this [Evaluation] as a [Pattern]
+    pub fn pattern(&self) -> Pattern {
+        self.pattern
+    }
+
+    /// Build an [Evaluation] for `guess` from an encoded [Pattern]
+    pub fn from_pattern(guess: &Word, pattern: Pattern) -> Self {
+        Self {
+            guess: *guess,
+            pattern,
+        }
+    }
+}
+
+/// Calculate the [Pattern] that `guess` would get if `solution` is the solution
+///
+/// This is the core of how wordle evaluates a guess: Letters in the correct spot are
+/// [matched](Status::Matched) first, then the remaining letters of the guess are marked as
+/// [existing](Status::Exists) from left to right, as long as the solution has unmatched letters of
+/// that kind left. All other letters get [Status::None].
+///
+/// This does not allocate for ASCII words, so it can be used in hot loops.
+pub fn pattern(solution: &str, guess: &str) -> Pattern {
+    if solution.is_ascii() && guess.is_ascii() {
+        pattern_of(solution.as_bytes(), guess.as_bytes())
+    } else {
+        let solution: Vec<char> = solution.chars().collect();
+        let guess: Vec<char> = guess.chars().collect();
+        pattern_of(&solution, &guess)
     }
 }
 
+fn pattern_of<T: PartialEq>(solution: &[T], guess: &[T]) -> Pattern {
+    debug_assert_eq!(solution.len(), guess.len());
+    debug_assert!(
+        guess.len() <= 20,
+        "patterns for words this long do not fit into a u32"
+    );
+    // bitmasks of the solution letters that are already used up
+    let mut used: u32 = 0;
+    let mut matched: u32 = 0;
+    let mut pattern: Pattern = 0;
+    let mut weight: Pattern = 1;
+    for (idx, (g, s)) in guess.iter().zip(solution).enumerate() {
+        if g == s {
+            used |= 1 << idx;
+            matched |= 1 << idx;
+            pattern += Status::Matched as Pattern * weight;
+        }
+        weight *= 3;
+    }
+    weight = 1;
+    for (idx, g) in guess.iter().enumerate() {
+        if matched & (1 << idx) == 0 {
+            if let Some(sidx) =
+                (0..solution.len()).find(|sidx| used & (1 << sidx) == 0 && solution[*sidx] == *g)
+            {
+                used |= 1 << sidx;
+                pattern += Status::Exists as Pattern * weight;
+            }
+        }
+        weight *= 3;
+    }
+    pattern
+}
+
+/// Check if `word` uses all hints that `guess` got with `pattern`, as required in hard mode
+///
+/// Letters that were [matched](Status::Matched) must be in the same spot again, and every letter
+/// that was revealed to be in the solution must be used at least as often as it was revealed.
+///
+/// Returns a description of the first hint that is not used, or [None] if `word` may be guessed.
+pub fn hard_mode_violation(word: &str, guess: &str, mut pattern: Pattern) -> Option<String> {
+    let word: Vec<char> = word.chars().collect();
+    let mut revealed: Vec<(char, usize)> = Vec::new();
+    for (idx, g) in guess.chars().enumerate() {
+        let status = pattern % 3;
+        pattern /= 3;
+        if status == Status::None as Pattern {
+            continue;
+        }
+        if status == Status::Matched as Pattern && word.get(idx) != Some(&g) {
+            return Some(format!(
+                "{} letter must be {}",
+                ordinal(idx + 1),
+                g.to_uppercase()
+            ));
+        }
+        match revealed.iter_mut().find(|(c, _)| *c == g) {
+            Some((_, n)) => *n += 1,
+            None => revealed.push((g, 1)),
+        }
+    }
+    for (c, n) in revealed {
+        if word.iter().filter(|w| **w == c).count() < n {
+            return Some(if n == 1 {
+                format!("Guess must contain {}", c.to_uppercase())
+            } else {
+                format!("Guess must contain {} at least {n} times", c.to_uppercase())
+            });
+        }
+    }
+    None
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
 impl IntoIterator for Evaluation {
     type Item = EvaluationUnit;
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.inner.into_iter()
+        self.units().collect::<Vec<_>>().into_iter()
     }
 }
 
-impl From<Vec<EvaluationUnit>> for Evaluation {
-    fn from(value: Vec<EvaluationUnit>) -> Self {
-        Self { inner: value }
+impl TryFrom<Vec<EvaluationUnit>> for Evaluation {
+    type Error = WordlistError;
+    /// # Errors
+    ///
+    /// Fails if the chars do not fit into a [Word].
+    fn try_from(value: Vec<EvaluationUnit>) -> Result<Self, Self::Error> {
+        let guess: Word = value.iter().map(|v| v.0).collect::<String>().parse()?;
+        let pattern = value
+            .iter()
+            .rev()
+            .fold(0, |acc, unit| acc * 3 + unit.1 as Pattern);
+        Ok(Self { guess, pattern })
     }
 }
 
 impl From<Evaluation> for Word {
     fn from(value: Evaluation) -> Self {
-        Word::from(value.inner.iter().map(|v| v.0).collect::<String>())
+        value.guess
     }
 }
 
 impl From<&Evaluation> for Word {
     fn from(value: &Evaluation) -> Self {
-        Word::from(value.inner.iter().map(|v| v.0).collect::<String>())
+        value.guess
     }
 }
@@ -0,0 +1,198 @@
+//! What is known about each letter, like the keyboard of a wordle game shows it
+
+use std::fmt::Display;
+
+use colored::Colorize;
+
+use super::constraints::Constraints;
+
+/// rows of the keyboard, letters that are not on it are shown in an extra row
+pub const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// The coarse state of a letter, as shown by the color of a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum LetterState {
+    /// the letter was not guessed yet
+    #[default]
+    Unknown,
+    /// the letter is not in the solution
+    Absent,
+    /// the letter is in the solution, but it's position is not known
+    Present,
+    /// the letter is known to be at at least one position
+    Confirmed,
+}
+
+/// Everything that is known about a single letter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterKnowledge {
+    letter: char,
+    state: LetterState,
+    confirmed: Vec<usize>,
+    excluded: Vec<usize>,
+    min: usize,
+    max: Option<usize>,
+}
+
+impl LetterKnowledge {
+    fn unknown(letter: char) -> Self {
+        Self {
+            letter,
+            state: LetterState::Unknown,
+            confirmed: Vec::new(),
+            excluded: Vec::new(),
+            min: 0,
+            max: None,
+        }
+    }
+
+    /// the letter this is about
+    pub fn letter(&self) -> char {
+        self.letter
+    }
+
+    /// the coarse state of the letter
+    pub fn state(&self) -> LetterState {
+        self.state
+    }
+
+    /// the positions where the letter is known to be
+    pub fn confirmed_at(&self) -> &[usize] {
+        &self.confirmed
+    }
+
+    /// the positions where the letter is known not to be
+    pub fn excluded_at(&self) -> &[usize] {
+        &self.excluded
+    }
+
+    /// how often the letter occurs in the solution at least
+    pub fn min_count(&self) -> usize {
+        self.min
+    }
+
+    /// how often the letter occurs in the solution at most, if that is known
+    pub fn max_count(&self) -> Option<usize> {
+        self.max
+    }
+}
+
+/// The knowledge about every letter that was guessed in a [Game](super::Game)
+///
+/// This is a per letter view of the [Constraints] of a game, see
+/// [Game::keyboard](super::Game::keyboard). Letters that were not guessed yet are
+/// [unknown](LetterState::Unknown).
+///
+/// The [Display] implementation shows a keyboard with colored keys, like the one in wordle.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Keyboard {
+    /// the letters that were guessed, in alphabetical order
+    letters: Vec<LetterKnowledge>,
+}
+
+impl Keyboard {
+    /// Collect what the [Constraints] tell about each letter
+    pub fn from_constraints(constraints: &Constraints) -> Self {
+        // every guessed letter is either matched or excluded where it was guessed
+        let mut guessed: Vec<char> = (0..constraints.length())
+            .flat_map(|idx| {
+                constraints.known()[idx]
+                    .into_iter()
+                    .chain(constraints.excluded_at(idx).iter().copied())
+            })
+            .collect();
+        guessed.sort();
+        guessed.dedup();
+        let letters = guessed
+            .into_iter()
+            .map(|c| {
+                let mut knowledge = LetterKnowledge::unknown(c);
+                for idx in 0..constraints.length() {
+                    if constraints.known()[idx] == Some(c) {
+                        knowledge.confirmed.push(idx);
+                    } else if constraints.excluded_at(idx).contains(&c) {
+                        knowledge.excluded.push(idx);
+                    }
+                }
+                if let Some((min, max)) = constraints.occurrences(c) {
+                    knowledge.min = min;
+                    knowledge.max = (max < constraints.length()).then_some(max);
+                }
+                knowledge.state = if !knowledge.confirmed.is_empty() {
+                    LetterState::Confirmed
+                } else if knowledge.min > 0 {
+                    LetterState::Present
+                } else if knowledge.max == Some(0) {
+                    LetterState::Absent
+                } else {
+                    LetterState::Unknown
+                };
+                knowledge
+            })
+            .collect();
+        Self { letters }
+    }
+
+    /// Get what is known about `letter`
+    pub fn get(&self, letter: char) -> LetterKnowledge {
+        self.letters
+            .iter()
+            .find(|l| l.letter == letter)
+            .cloned()
+            .unwrap_or_else(|| LetterKnowledge::unknown(letter))
+    }
+
+    /// Get the coarse state of `letter`
+    pub fn state(&self, letter: char) -> LetterState {
+        self.letters
+            .iter()
+            .find(|l| l.letter == letter)
+            .map(|l| l.state)
+            .unwrap_or_default()
+    }
+
+    /// all letters that were guessed, in alphabetical order
+    pub fn letters(&self) -> impl Iterator<Item = &LetterKnowledge> {
+        self.letters.iter()
+    }
+
+    /// all letters with the [LetterState], in alphabetical order
+    pub fn with_state(&self, state: LetterState) -> impl Iterator<Item = char> + '_ {
+        self.letters
+            .iter()
+            .filter(move |l| l.state == state)
+            .map(|l| l.letter)
+    }
+}
+
+impl Display for Keyboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let extra: String = self
+            .letters
+            .iter()
+            .map(|l| l.letter)
+            .filter(|c| !KEYBOARD_ROWS.iter().any(|r| r.contains(*c)))
+            .collect();
+        let mut rows: Vec<&str> = KEYBOARD_ROWS.to_vec();
+        if !extra.is_empty() {
+            rows.push(&extra);
+        }
+        for (idx, row) in rows.iter().enumerate() {
+            write!(f, "{}", " ".repeat(idx))?;
+            for c in row.chars() {
+                let key = c.to_uppercase().to_string();
+                let key = match self.state(c) {
+                    LetterState::Unknown => key.normal(),
+                    LetterState::Absent => key.dimmed().strikethrough(),
+                    LetterState::Present => key.black().on_yellow(),
+                    LetterState::Confirmed => key.black().on_green(),
+                };
+                write!(f, "{key} ")?;
+            }
+            if idx + 1 < rows.len() {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+//! Precomputed [Patterns](Pattern) for every combination of guess and solution
+
+use std::collections::HashMap;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use libpt::log::{debug, info, warn};
+use rayon::prelude::*;
+
+use crate::error::{GameError, WResult, WordlistError};
+use crate::wlist::word::Word;
+use crate::wlist::WordList;
+
+use super::evaluation::{pattern, Pattern};
+
+/// How a [Pattern] is stored in a [PatternMatrix]
+pub type CompactPattern = u16;
+
+/// Words longer than this have too many possible patterns to fit into a [CompactPattern]
+pub const MAX_PRECOMPUTE_LENGTH: usize = 10;
+
+const MAGIC: &[u8; 4] = b"WAPM";
+const FORMAT_VERSION: u8 = 1;
+
+/// A dense matrix of the [Pattern] for every guess and every solution of a [WordList]
+///
+/// The rows are all words of the wordlist that can be guessed, the columns are all
+/// [solutions](WordList::solutions). Looking up a pattern by index is much cheaper than
+/// [calculating it](pattern), which makes a big difference for solvers that need to look at
+/// every combination of guesses and solutions, like the
+/// [EntropySolver](crate::solve::EntropySolver).
+///
+/// Building the matrix takes a while for large wordlists, so it can be [saved](Self::save) to and
+/// [loaded](Self::load) from a binary file, see [cached](Self::cached).
+#[derive(Clone, PartialEq)]
+pub struct PatternMatrix {
+    wordlist: String,
+    length: usize,
+    guesses: Vec<Word>,
+    solutions: Vec<Word>,
+    guess_index: HashMap<Word, usize>,
+    solution_index: HashMap<Word, usize>,
+    data: Vec<CompactPattern>,
+}
+
+impl PatternMatrix {
+    /// Calculate the [PatternMatrix] for all words of a wordlist with `length`
+    ///
+    /// # Errors
+    ///
+    /// Fails with [GameError::CannotPrecompute] if the words are longer than
+    /// [MAX_PRECOMPUTE_LENGTH].
+    pub fn build<WL: WordList>(wl: &WL, length: usize) -> WResult<Self> {
+        if length > MAX_PRECOMPUTE_LENGTH {
+            return Err(GameError::CannotPrecompute(length).into());
+        }
+        let (guesses, solutions) = Self::words_of(wl, length);
+        info!(
+            "precomputing patterns for {} guesses and {} solutions",
+            guesses.len(),
+            solutions.len()
+        );
+        let mut data: Vec<CompactPattern> = vec![0; guesses.len() * solutions.len()];
+        if !solutions.is_empty() {
+            data.par_chunks_mut(solutions.len())
+                .zip(guesses.par_iter())
+                .for_each(|(row, guess)| {
+                    for (cell, solution) in row.iter_mut().zip(&solutions) {
+                        *cell = pattern(solution, guess) as CompactPattern;
+                    }
+                });
+        }
+        debug!("finished precomputing patterns");
+        Ok(Self::from_parts(
+            wl.name().to_string(),
+            length,
+            guesses,
+            solutions,
+            data,
+        ))
+    }
+
+    /// Load the [PatternMatrix] from `dir` if it was saved there before, otherwise build it and
+    /// save it there
+    ///
+    /// The file name is based on the [name](WordList::name) of the wordlist and the length. If
+    /// the cached file does not fit the wordlist, it is rebuilt.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the matrix can not be built or the file can not be written.
+    pub fn cached<WL: WordList, P: AsRef<Path>>(wl: &WL, length: usize, dir: P) -> WResult<Self> {
+        let path = Self::cache_path(wl, length, dir.as_ref());
+        if path.exists() {
+            match Self::load(&path) {
+                Ok(matrix) if matrix.fits(wl, length) => {
+                    info!("loaded precomputed patterns from {}", path.display());
+                    return Ok(matrix);
+                }
+                Ok(_) => warn!("{} is for another wordlist, rebuilding", path.display()),
+                Err(e) => warn!("could not load {}: {e}", path.display()),
+            }
+        }
+        let matrix = Self::build(wl, length)?;
+        std::fs::create_dir_all(dir)?;
+        matrix.save(&path)?;
+        info!("saved precomputed patterns to {}", path.display());
+        Ok(matrix)
+    }
+
+    /// Where [cached](Self::cached) stores the matrix for a wordlist in `dir`
+    pub fn cache_path<WL: WordList>(wl: &WL, length: usize, dir: &Path) -> PathBuf {
+        dir.join(format!(
+            "{}-{length}.patterns",
+            crate::wlist::sanitized_name(wl.name())
+        ))
+    }
+
+    /// Check if this matrix was built for exactly these words
+    pub fn fits<WL: WordList>(&self, wl: &WL, length: usize) -> bool {
+        if self.wordlist != wl.name() || self.length != length {
+            return false;
+        }
+        let (guesses, solutions) = Self::words_of(wl, length);
+        guesses == self.guesses && solutions == self.solutions
+    }
+
+    /// Save the matrix in a compact binary format
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can not be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> WResult<()> {
+        let mut w = BufWriter::new(std::fs::File::create(path)?);
+        w.write_all(MAGIC)?;
+        w.write_all(&[FORMAT_VERSION])?;
+        write_bytes(&mut w, self.wordlist.as_bytes())?;
+        w.write_all(&(self.length as u32).to_le_bytes())?;
+        write_bytes(&mut w, join_words(&self.guesses).as_bytes())?;
+        write_bytes(&mut w, join_words(&self.solutions).as_bytes())?;
+        for p in &self.data {
+            w.write_all(&p.to_le_bytes())?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+
+    /// Load a matrix that was [saved](Self::save) before
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can not be read or is not a valid pattern matrix.
+    pub fn load<P: AsRef<Path>>(path: P) -> WResult<Self> {
+        let mut r = BufReader::new(std::fs::File::open(path)?);
+        let mut magic = [0; 5];
+        r.read_exact(&mut magic)?;
+        if magic[..4] != *MAGIC || magic[4] != FORMAT_VERSION {
+            return Err(bad_file("unknown file format or version"));
+        }
+        let wordlist = read_string(&mut r)?;
+        let length = read_u32(&mut r)? as usize;
+        let guesses = split_words(&read_string(&mut r)?)?;
+        let solutions = split_words(&read_string(&mut r)?)?;
+        let mut raw = Vec::new();
+        r.read_to_end(&mut raw)?;
+        if raw.len() != guesses.len() * solutions.len() * size_of::<CompactPattern>() {
+            return Err(bad_file("the matrix has the wrong size"));
+        }
+        let data = raw
+            .chunks_exact(size_of::<CompactPattern>())
+            .map(|b| CompactPattern::from_le_bytes([b[0], b[1]]))
+            .collect();
+        Ok(Self::from_parts(wordlist, length, guesses, solutions, data))
+    }
+
+    /// the words that can be guessed, in the order of the rows
+    pub fn guesses(&self) -> &[Word] {
+        &self.guesses
+    }
+
+    /// the solutions, in the order of the columns
+    pub fn solutions(&self) -> &[Word] {
+        &self.solutions
+    }
+
+    /// the length of the words in this matrix
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// the row of a guess
+    pub fn guess_index(&self, guess: &Word) -> Option<usize> {
+        self.guess_index.get(guess).copied()
+    }
+
+    /// the column of a solution
+    pub fn solution_index(&self, solution: &Word) -> Option<usize> {
+        self.solution_index.get(solution).copied()
+    }
+
+    /// the patterns of a guess for all solutions
+    pub fn row(&self, guess_idx: usize) -> &[CompactPattern] {
+        let n = self.solutions.len();
+        &self.data[guess_idx * n..(guess_idx + 1) * n]
+    }
+
+    /// the pattern by row and column
+    pub fn pattern_at(&self, guess_idx: usize, solution_idx: usize) -> Pattern {
+        self.data[guess_idx * self.solutions.len() + solution_idx] as Pattern
+    }
+
+    /// Look up the [Pattern] of `guess` for `solution`
+    ///
+    /// Returns [None] if either word is not in the matrix.
+    pub fn get(&self, guess: &Word, solution: &Word) -> Option<Pattern> {
+        Some(self.pattern_at(self.guess_index(guess)?, self.solution_index(solution)?))
+    }
+
+    /// Like [get](Self::get), but calculates the pattern if it was not precomputed
+    pub fn get_or_calculate(&self, guess: &Word, solution: &Word) -> Pattern {
+        self.get(guess, solution)
+            .unwrap_or_else(|| pattern(solution, guess))
+    }
+
+    fn from_parts(
+        wordlist: String,
+        length: usize,
+        guesses: Vec<Word>,
+        solutions: Vec<Word>,
+        data: Vec<CompactPattern>,
+    ) -> Self {
+        let index = |words: &[Word]| -> HashMap<Word, usize> {
+            words.iter().enumerate().map(|(i, w)| (*w, i)).collect()
+        };
+        Self {
+            guess_index: index(&guesses),
+            solution_index: index(&solutions),
+            wordlist,
+            length,
+            guesses,
+            solutions,
+            data,
+        }
+    }
+
+    /// all guesses and all solutions of the wordlist with `length`, sorted
+    fn words_of<WL: WordList>(wl: &WL, length: usize) -> (Vec<Word>, Vec<Word>) {
+        let mut guesses: Vec<Word> = wl
+            .wordmap()
+            .keys()
+            .filter(|w| w.char_len() == length)
+            .cloned()
+            .collect();
+        guesses.sort();
+        let mut solutions: Vec<Word> = wl
+            .solutions()
+            .into_iter()
+            .map(|s| s.0)
+            .filter(|w| w.char_len() == length)
+            .collect();
+        solutions.sort();
+        (guesses, solutions)
+    }
+}
+
+impl std::fmt::Debug for PatternMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PatternMatrix")
+            .field("wordlist", &self.wordlist)
+            .field("length", &self.length)
+            .field("guesses", &self.guesses.len())
+            .field("solutions", &self.solutions.len())
+            .finish()
+    }
+}
+
+fn bad_file(msg: &str) -> crate::error::Error {
+    WordlistError::from(std::io::Error::new(std::io::ErrorKind::InvalidData, msg)).into()
+}
+
+fn write_bytes(w: &mut impl Write, bytes: &[u8]) -> std::io::Result<()> {
+    w.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    w.write_all(bytes)
+}
+
+fn read_u32(r: &mut impl Read) -> std::io::Result<u32> {
+    let mut buf = [0; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_string(r: &mut impl Read) -> WResult<String> {
+    let mut buf = vec![0; read_u32(r)? as usize];
+    r.read_exact(&mut buf)?;
+    String::from_utf8(buf).map_err(|_| bad_file("words are not valid UTF-8"))
+}
+
+fn join_words(words: &[Word]) -> String {
+    words
+        .iter()
+        .map(Word::as_str)
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+fn split_words(s: &str) -> WResult<Vec<Word>> {
+    if s.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(s.split('\n').map(str::parse).collect::<Result<_, _>>()?)
+}
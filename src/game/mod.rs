@@ -1,21 +1,42 @@
 use core::panic;
+use std::collections::BTreeMap;
 use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 use crate::error::*;
 use crate::wlist::word::{Word, WordData};
 use crate::wlist::WordList;
+use crate::{MAX_WORD_LENGTH, MIN_WORD_LENGTH};
 
-use libpt::log::{debug, trace};
+use libpt::log::{debug, trace, warn};
 
 pub mod response;
 use response::GuessResponse;
 
+pub mod constraints;
+
+pub mod keyboard;
+
+pub mod record;
+
+pub mod share;
+
 pub mod evaluation;
 
+pub mod matrix;
+
+pub mod multi;
+
 pub mod summary;
 
-use self::evaluation::Evaluation;
-use self::response::Status;
+use self::constraints::Constraints;
+use self::evaluation::{Evaluation, Pattern};
+use self::keyboard::Keyboard;
+use self::matrix::PatternMatrix;
+use self::multi::MultiGame;
+use self::record::GameRecord;
+use self::share::ShareGrid;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Game<'wl, WL>
@@ -23,13 +44,18 @@ where
     WL: WordList,
 {
     length: usize,
-    precompute: bool,
+    patterns: Option<Arc<PatternMatrix>>,
     max_steps: usize,
+    hard_mode: bool,
+    adversarial: bool,
+    /// solutions that fit all responses so far, only tracked in adversarial games
+    candidates: Vec<WordData>,
+    /// what the responses so far tell about the solution
+    constraints: Constraints,
     step: usize,
     solution: Option<WordData>,
     wordlist: &'wl WL,
     responses: Vec<GuessResponse>,
-    // TODO: keep track of the letters the user has tried
 }
 
 impl<'wl, WL: WordList> Game<'wl, WL> {
@@ -48,38 +74,60 @@ impl<'wl, WL: WordList> Game<'wl, WL> {
     ///
     /// # Errors
     ///
-    /// No Errors
+    /// Fails with [GameError::UnsupportedLength] if `length` is not from [MIN_WORD_LENGTH] to
+    /// [MAX_WORD_LENGTH], or with [GameError::NoWordsWithLength] if the wordlist has no words
+    /// that long, or no solutions that long if one is generated.
     ///
     /// # Parameters
     ///
     /// `length` - how many chars the solution has
-    /// `precompute` -  how many chars the solution has
+    /// `patterns` -  precomputed patterns to look up evaluations in
     /// `max_steps` -  how many tries the player has
-    /// `precompute` -  how many chars the solution has
+    /// `hard_mode` -  do guesses need to use all revealed hints?
+    /// `adversarial` -  should the game avoid committing to a solution?
     /// `wlist` -  which wordlist to use
-    /// `generate_solution` -  should the game have a randomly generated solution?
-
+    /// `generate_solution` -  should the game have a randomly generated solution? Ignored for
+    /// adversarial games.
+    #[allow(clippy::too_many_arguments)]
     pub fn build(
         length: usize,
-        precompute: bool,
+        patterns: Option<Arc<PatternMatrix>>,
         max_steps: usize,
+        hard_mode: bool,
+        adversarial: bool,
         wlist: &'wl WL,
         generate_solution: bool,
     ) -> GameResult<Self> {
-        // TODO: check if the length is in the range bounds of the wordlist
-        let game: Game<'wl, WL> = Game {
+        if !(MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&length) {
+            return Err(GameError::UnsupportedLength(length));
+        }
+        if !wlist.has_length(length) {
+            return Err(GameError::NoWordsWithLength(length));
+        }
+        let mut game: Game<'wl, WL> = Game {
             length,
-            precompute,
+            patterns,
             max_steps,
+            hard_mode,
+            adversarial,
+            candidates: Vec::new(),
+            constraints: Constraints::new(length),
             step: 0,
-            solution: if generate_solution {
-                Some(wlist.rand_solution())
+            solution: if generate_solution && !adversarial {
+                Some(
+                    wlist
+                        .rand_solution(length)
+                        .ok_or(GameError::NoWordsWithLength(length))?,
+                )
             } else {
                 None
             },
             wordlist: wlist,
             responses: Vec::new(),
         };
+        if adversarial {
+            game.reset_candidates();
+        }
 
         Ok(game)
     }
@@ -100,58 +148,187 @@ impl<'wl, WL: WordList> Game<'wl, WL> {
     /// # Errors
     ///
     /// This function will return an error if the length of the [Word] is wrong It will also error
-    /// if the game is finished.
+    /// if the game is finished. In [hard mode](GameBuilder::hard_mode), it will return
+    /// [GameError::HardModeViolation] if the guess does not use all revealed hints.
     pub fn guess(&mut self, guess: &Word, eval: Option<Evaluation>) -> GameResult<GuessResponse> {
-        if guess.len() != self.length {
-            return Err(GameError::GuessHasWrongLength(guess.len()));
+        self.check_guess(guess)?;
+        self.step += 1;
+
+        let evaluation = match (eval, self.solution) {
+            (_, Some(solution)) => self.evaluate(solution, guess),
+            (_, None) if self.adversarial => self.evaluate_adversarial(guess),
+            (Some(eval), None) => eval,
+            (None, None) => {
+                panic!("there is neither an evaluation nor a predefined solution for this guess")
+            }
+        };
+        let response = GuessResponse::new(guess, evaluation, self);
+        self.constraints.add_response(&response);
+        self.responses.push(response.clone());
+        Ok(response)
+    }
+
+    /// Check if `guess` can be made next, see [guess](Game::guess) for the errors
+    pub(crate) fn check_guess(&self, guess: &Word) -> GameResult<()> {
+        if guess.char_len() != self.length {
+            return Err(GameError::GuessHasWrongLength(guess.char_len()));
         }
-        if self.finished() || self.step > self.max_steps {
+        if self.finished() || self.step >= self.max_steps {
             return Err(GameError::TryingToPlayAFinishedGame);
         }
         if self.wordlist.get_word(guess).is_none() {
-            return Err(GameError::WordNotInWordlist(guess.to_string()));
+            return Err(GameError::WordNotInWordlist(*guess));
         }
-        self.step += 1;
+        if self.hard_mode {
+            self.check_hard_mode(guess)?;
+        }
+        Ok(())
+    }
 
-        let response;
-        if eval.is_some() && self.solution.is_none() {
-            response = GuessResponse::new(guess, eval.unwrap(), self);
-        } else if let Some(solution) = self.solution.clone() {
-            response = GuessResponse::new(guess, Self::evaluate(solution, guess), self);
-        } else {
-            panic!("there is neither an evaluation nor a predefined solution for this guess");
+    /// Generates the [Evaluation] that keeps the most candidates for an adversarial game
+    ///
+    /// The candidates are grouped by the [Pattern] they would give for `guess`, and the biggest
+    /// group is kept. If groups are equally big, the one with less hints is kept. Once a single
+    /// candidate is left, it becomes the [solution](Game::solution).
+    fn evaluate_adversarial(&mut self, guess: &Word) -> Evaluation {
+        let mut buckets: BTreeMap<Pattern, Vec<WordData>> = BTreeMap::new();
+        for candidate in std::mem::take(&mut self.candidates) {
+            let pattern = match &self.patterns {
+                Some(patterns) => patterns.get_or_calculate(guess, &candidate.0),
+                None => evaluation::pattern(&candidate.0, guess),
+            };
+            buckets.entry(pattern).or_default().push(candidate);
+        }
+        // matched letters count as two hints, existing ones as one
+        let hints = |mut p: Pattern| {
+            let mut sum = 0;
+            while p > 0 {
+                sum += p % 3;
+                p /= 3;
+            }
+            sum
+        };
+        let Some((pattern, candidates)) = buckets.into_iter().max_by(|a, b| {
+            a.1.len()
+                .cmp(&b.1.len())
+                .then(hints(b.0).cmp(&hints(a.0)))
+                .then(b.0.cmp(&a.0))
+        }) else {
+            // there were no candidates to begin with
+            return Evaluation::from_pattern(guess, 0);
+        };
+        trace!("adversary keeps {} candidates", candidates.len());
+        self.candidates = candidates;
+        if self.candidates.len() == 1 {
+            self.solution = Some(self.candidates[0]);
+        }
+        Evaluation::from_pattern(guess, pattern)
+    }
+
+    /// Find the candidates of an adversarial game from scratch, using the responses so far
+    fn reset_candidates(&mut self) {
+        self.solution = None;
+        self.candidates = self.wordlist.solutions_matching(&self.constraints);
+        if self.candidates.len() == 1 {
+            self.solution = Some(self.candidates[0]);
         }
-        self.responses.push(response.clone());
-        Ok(response)
     }
 
     /// Generates an [Evaluation] for a given solution and guess.
-    pub(crate) fn evaluate(solution: WordData, guess: &Word) -> Evaluation {
-        let mut evaluation = Vec::new();
-        let mut status: Status;
-        let mut buf = solution.0.clone();
-        for ((idx, c_guess), c_sol) in guess.chars().enumerate().zip(solution.0.chars()) {
-            if c_guess == c_sol {
-                status = Status::Matched;
-                buf.replace_range(idx..idx + 1, "_");
-            } else if buf.contains(c_guess) {
-                status = Status::Exists;
-                buf = buf.replacen(c_guess, "_", 1);
-            } else {
-                status = Status::None
+    ///
+    /// The evaluation is looked up in the [PatternMatrix] if the game has one.
+    pub(crate) fn evaluate(&self, solution: WordData, guess: &Word) -> Evaluation {
+        let pattern = match &self.patterns {
+            Some(patterns) => patterns.get_or_calculate(guess, &solution.0),
+            None => evaluation::pattern(&solution.0, guess),
+        };
+        Evaluation::from_pattern(guess, pattern)
+    }
+
+    /// Check if `guess` uses all hints revealed so far, as required in hard mode
+    ///
+    /// This checks the hints even if the game is not in [hard mode](Game::hard_mode).
+    ///
+    /// # Errors
+    ///
+    /// Returns [GameError::HardModeViolation] naming the first hint that is not used.
+    pub fn check_hard_mode(&self, guess: &Word) -> GameResult<()> {
+        for response in &self.responses {
+            if let Some(violation) = evaluation::hard_mode_violation(
+                guess,
+                response.guess(),
+                response.evaluation().pattern(),
+            ) {
+                return Err(GameError::HardModeViolation(violation));
             }
-            evaluation.push((c_guess, status));
         }
-        evaluation.into()
+        Ok(())
+    }
+
+    /// Check if the rules of this game allow guessing `guess` next
+    ///
+    /// This is always true unless the game is in [hard mode](Game::hard_mode). It does not check
+    /// the length of the guess or whether it is in the wordlist.
+    pub fn allows(&self, guess: &Word) -> bool {
+        !self.hard_mode || self.check_hard_mode(guess).is_ok()
     }
 
     /// discard the last n responses
     pub fn undo(&mut self, n: usize) -> WResult<()> {
+        let n = n.min(self.responses.len());
         self.responses
             .drain(self.responses.len() - n..self.responses.len());
+        self.step -= n.min(self.step);
+        self.constraints = Constraints::from_responses(self.length, &self.responses);
+        if self.adversarial {
+            self.reset_candidates();
+        }
         Ok(())
     }
 
+    /// do guesses need to use all revealed hints?
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// does the game avoid committing to a solution?
+    pub fn adversarial(&self) -> bool {
+        self.adversarial
+    }
+
+    /// get the solutions an adversarial game can still choose from
+    ///
+    /// This is empty for games that are not [adversarial](Game::adversarial).
+    pub fn candidates(&self) -> &[WordData] {
+        &self.candidates
+    }
+
+    /// get what the responses so far tell about the solution
+    ///
+    /// This is updated with every guess, so the words that might still be the solution can be
+    /// found cheaply, see [WordList::words_matching].
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    /// get what is known about each letter, like the keyboard of wordle shows it
+    ///
+    /// This is a per letter view of the [constraints](Game::constraints), so it is updated with
+    /// every guess and rewound by [undo](Game::undo) as well.
+    pub fn keyboard(&self) -> Keyboard {
+        Keyboard::from_constraints(&self.constraints)
+    }
+
+    /// Make a [GameRecord] of the game so far, that can be saved and [replayed](GameRecord::replay)
+    pub fn record(&self) -> GameRecord {
+        GameRecord::of(self)
+    }
+
+    /// Make the [ShareGrid] of the game so far, the emoji grid that is posted to share results
+    pub fn share(&self) -> ShareGrid {
+        ShareGrid::of(self)
+    }
+
     /// get how many characters the words have for this game
     pub fn length(&self) -> usize {
         self.length
@@ -206,6 +383,12 @@ impl<'wl, WL: WordList> Game<'wl, WL> {
         self.wordlist
     }
 
+    /// get the precomputed [PatternMatrix], if the game was built with
+    /// [precompute](GameBuilder::precompute)
+    pub fn patterns(&self) -> Option<&PatternMatrix> {
+        self.patterns.as_deref()
+    }
+
     /// get the [Words](Word) that have already been tried
     pub(crate) fn made_guesses(&self) -> Vec<&Word> {
         self.responses.iter().map(|r| r.guess()).collect()
@@ -252,7 +435,12 @@ impl<'wl, WL: WordList> Game<'wl, WL> {
 pub struct GameBuilder<'wl, WL: WordList> {
     length: usize,
     precompute: bool,
+    precompute_cache: Option<PathBuf>,
+    /// shared by all games of this builder, only computed once
+    patterns: Arc<OnceLock<Option<Arc<PatternMatrix>>>>,
     max_steps: usize,
+    hard_mode: bool,
+    adversarial: bool,
     wordlist: &'wl WL,
     generate_solution: bool,
     solution: Option<WordData>,
@@ -266,7 +454,11 @@ impl<'wl, WL: WordList> GameBuilder<'wl, WL> {
         Self {
             length: super::DEFAULT_WORD_LENGTH,
             precompute: false,
+            precompute_cache: None,
+            patterns: Arc::new(OnceLock::new()),
             max_steps: super::DEFAULT_MAX_STEPS,
+            hard_mode: false,
+            adversarial: false,
             wordlist: wl,
             generate_solution,
             solution: None,
@@ -274,35 +466,112 @@ impl<'wl, WL: WordList> GameBuilder<'wl, WL> {
     }
 
     /// build a [`Game`] with the stored configuration
-    pub fn build(&'wl self) -> GameResult<Game<'wl, WL>> {
+    pub fn build(&self) -> GameResult<Game<'wl, WL>> {
         trace!("{:#?}", self);
         let mut game: Game<WL> = Game::build(
             self.length,
-            self.precompute,
+            self.patterns(),
             self.max_steps,
+            self.hard_mode,
+            self.adversarial,
             self.wordlist,
             self.generate_solution,
         )?;
-        if self.solution.is_some() {
-            game.set_solution(self.solution.clone())
+        if self.solution.is_some() && !self.adversarial {
+            game.set_solution(self.solution)
         }
         Ok(game)
     }
 
+    /// build a [`MultiGame`] with `boards` boards and the stored configuration
+    ///
+    /// Generated solutions are different for each board, as long as the wordlist has enough of
+    /// them. The player gets one extra guess for every board after the first, so with the default
+    /// [max_steps](Self::max_steps) that's 7 guesses for Dordle, 9 for Quordle and 13 for
+    /// Octordle.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [GameError::NoBoards] if `boards` is 0.
+    pub fn build_multi(&self, boards: usize) -> GameResult<MultiGame<'wl, WL>> {
+        if boards == 0 {
+            return Err(GameError::NoBoards);
+        }
+        let builder = self.clone().max_steps(self.max_steps + boards - 1);
+        let reroll = self.generate_solution && self.solution.is_none() && !self.adversarial;
+        let mut games: Vec<Game<'wl, WL>> = Vec::with_capacity(boards);
+        for _ in 0..boards {
+            let mut game = builder.build()?;
+            // give up eventually, the wordlist might not have enough solutions
+            for _ in 0..100 {
+                match game.solution() {
+                    Some(s) if reroll && games.iter().any(|g| g.solution() == Some(s)) => {
+                        game.set_solution(self.wordlist.rand_solution(self.length))
+                    }
+                    _ => break,
+                }
+            }
+            games.push(game);
+        }
+        MultiGame::build(games)
+    }
+
+    /// get the [PatternMatrix] for the games of this builder
+    ///
+    /// The matrix is computed (or loaded from the [cache](Self::precompute_cache)) the first time
+    /// this is called, and then shared by all games. If [precompute](Self::precompute) is
+    /// disabled or the patterns can not be precomputed, this is [None].
+    pub fn patterns(&self) -> Option<Arc<PatternMatrix>> {
+        if !self.precompute {
+            return None;
+        }
+        self.patterns
+            .get_or_init(|| {
+                let matrix = match &self.precompute_cache {
+                    Some(dir) => PatternMatrix::cached(self.wordlist, self.length, dir),
+                    None => PatternMatrix::build(self.wordlist, self.length),
+                };
+                match matrix {
+                    Ok(matrix) => Some(Arc::new(matrix)),
+                    Err(e) => {
+                        warn!("not using precomputed patterns: {e}");
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
     /// Should we pre compute all possible answers? This will make startup significantly more
     /// expensive, but reduce the computing time while playing.
     ///
+    /// The answers are stored in a [PatternMatrix], which is shared by all games of the builder.
+    ///
     /// Default is [`false`]
     pub fn precompute(mut self, precompute: bool) -> Self {
         self.precompute = precompute;
         self
     }
 
+    /// Store the precomputed answers in this directory, so they only need to be computed once
+    /// per wordlist and word length, see [PatternMatrix::cached]
+    ///
+    /// Only used if [precompute](Self::precompute) is enabled.
+    ///
+    /// Default is [None]
+    pub fn precompute_cache<P: AsRef<Path>>(mut self, dir: Option<P>) -> Self {
+        self.precompute_cache = dir.map(|d| d.as_ref().to_path_buf());
+        self.patterns = Arc::new(OnceLock::new());
+        self
+    }
+
     /// Set the length of words for the game
     ///
-    /// Default is [`super::DEFAULT_WORD_LENGTH`]
+    /// Default is [`super::DEFAULT_WORD_LENGTH`]. Games can have from [`super::MIN_WORD_LENGTH`]
+    /// to [`super::MAX_WORD_LENGTH`] letters, if the wordlist has words that long.
     pub fn length(mut self, length: usize) -> Self {
         self.length = length;
+        self.patterns = Arc::new(OnceLock::new());
         self
     }
 
@@ -315,12 +584,37 @@ impl<'wl, WL: WordList> GameBuilder<'wl, WL> {
         self
     }
 
+    /// Play in hard mode: Every guess must use all hints revealed so far, that is, matched
+    /// letters must stay in place and letters that exist in the solution must be used again.
+    ///
+    /// Guesses that break this rule are rejected with [GameError::HardModeViolation].
+    ///
+    /// Default is [`false`]
+    pub fn hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// Play against an adversary, like in "Absurdle": The game does not pick a solution, instead
+    /// it answers every guess so that as many solutions as possible are left. This is the worst
+    /// case for any [Solver](crate::solve::Solver).
+    ///
+    /// Adversarial games never have a [generated solution](Self::generate_solution) or a
+    /// [predefined one](Self::solution).
+    ///
+    /// Default is [`false`]
+    pub fn adversarial(mut self, adversarial: bool) -> Self {
+        self.adversarial = adversarial;
+        self
+    }
+
     /// Set the wordlist for the builder
     ///
     /// The builder can be used multiple times. Each [`Game`] will have a immutable reference to
     /// `wl`.
     pub fn wordlist(mut self, wl: &'wl WL) -> Self {
         self.wordlist = wl;
+        self.patterns = Arc::new(OnceLock::new());
         self
     }
 
@@ -370,6 +664,6 @@ impl<WL: WordList> Display for Game<'_, WL> {
             }
             write!(f, "\", ")?;
         }
-        Ok(())
+        write!(f, "\nkeyboard:\n{}", self.keyboard())
     }
 }
@@ -0,0 +1,131 @@
+//! Play multiple boards at once, like in Dordle, Quordle or Octordle
+
+use crate::error::{GameError, GameResult, WResult};
+use crate::wlist::word::{Word, WordData};
+use crate::wlist::WordList;
+
+use super::response::GuessResponse;
+use super::Game;
+
+/// Multiple [Games](Game) of wordle that are played with the same guesses
+///
+/// Every guess is made on all boards that are not solved yet. The game is won once every board is
+/// solved, and lost once a board runs out of guesses.
+///
+/// Build it with [GameBuilder::build_multi](super::GameBuilder::build_multi).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiGame<'wl, WL>
+where
+    WL: WordList,
+{
+    boards: Vec<Game<'wl, WL>>,
+    step: usize,
+}
+
+impl<'wl, WL: WordList> MultiGame<'wl, WL> {
+    /// Create a [MultiGame] from the [Games](Game) for the boards
+    ///
+    /// Don't use this method directly, instead, make use of
+    /// [GameBuilder::build_multi](super::GameBuilder::build_multi).
+    ///
+    /// # Errors
+    ///
+    /// Fails with [GameError::NoBoards] if there are no boards.
+    pub fn build(boards: Vec<Game<'wl, WL>>) -> GameResult<Self> {
+        if boards.is_empty() {
+            return Err(GameError::NoBoards);
+        }
+        Ok(Self { boards, step: 0 })
+    }
+
+    /// set the solution of a board, can be used for testing
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no board with this index.
+    pub fn set_solution(&mut self, board: usize, sol: Option<WordData>) {
+        self.boards[board].set_solution(sol);
+    }
+
+    /// Make a new guess on all boards that are not solved yet
+    ///
+    /// Returns the [GuessResponse] of each board, or [None] for boards that were solved before.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [Game::guess] if the guess can not be made on one of the boards. In that case,
+    /// no board is changed.
+    pub fn guess(&mut self, guess: &Word) -> GameResult<Vec<Option<GuessResponse>>> {
+        if self.finished() {
+            return Err(GameError::TryingToPlayAFinishedGame);
+        }
+        for board in self.unsolved() {
+            board.check_guess(guess)?;
+        }
+        self.step += 1;
+        let mut responses = Vec::with_capacity(self.boards.len());
+        for board in self.boards.iter_mut() {
+            responses.push(if board.won() {
+                None
+            } else {
+                Some(board.guess(guess, None)?)
+            });
+        }
+        Ok(responses)
+    }
+
+    /// discard the last n guesses
+    pub fn undo(&mut self, n: usize) -> WResult<()> {
+        self.step -= n.min(self.step);
+        for board in self.boards.iter_mut() {
+            let made = board.responses().len();
+            board.undo(made - made.min(self.step))?;
+        }
+        Ok(())
+    }
+
+    /// get all boards
+    pub fn boards(&self) -> &[Game<'wl, WL>] {
+        &self.boards
+    }
+
+    /// get the boards that are not solved yet
+    pub fn unsolved(&self) -> impl Iterator<Item = &Game<'wl, WL>> {
+        self.boards.iter().filter(|b| !b.won())
+    }
+
+    /// get how many boards are solved
+    pub fn solved(&self) -> usize {
+        self.boards.iter().filter(|b| b.won()).count()
+    }
+
+    /// get how many guesses were made
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// get how many tries the player has
+    pub fn max_steps(&self) -> usize {
+        self.boards[0].max_steps()
+    }
+
+    /// get how many characters the words have for this game
+    pub fn length(&self) -> usize {
+        self.boards[0].length()
+    }
+
+    /// get the [WordList] for this game
+    pub fn wordlist(&self) -> &WL {
+        self.boards[0].wordlist()
+    }
+
+    /// true if all boards are solved or a board ran out of guesses
+    pub fn finished(&self) -> bool {
+        self.won() || self.boards.iter().any(|b| b.finished() && !b.won())
+    }
+
+    /// true if all boards are solved
+    pub fn won(&self) -> bool {
+        self.boards.iter().all(|b| b.won())
+    }
+}
@@ -0,0 +1,418 @@
+//! Records of games that can be saved, shared and replayed
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use libpt::log::warn;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GameError, WResult};
+use crate::wlist::word::Word;
+use crate::wlist::WordList;
+
+use super::evaluation::Evaluation;
+use super::response::GuessResponse;
+use super::{Game, GameBuilder};
+
+/// The version of the record format that is written
+///
+/// Records with a higher version can not be read.
+pub const RECORD_VERSION: u32 = 1;
+
+/// the first word of the [text form](GameRecord#text-form) of a record
+const TEXT_MAGIC: &str = "#wordle-analyzer";
+
+/// A guess of a [GameRecord] and how it was evaluated
+///
+/// The evaluation is stored in the format of [Evaluation::build], like `xxpxc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(into = "RawGuess", try_from = "RawGuess")
+)]
+pub struct RecordedGuess {
+    evaluation: Evaluation,
+}
+
+impl RecordedGuess {
+    /// the word that was guessed
+    pub fn guess(&self) -> Word {
+        self.evaluation.guess()
+    }
+
+    /// the [Evaluation] of the guess
+    pub fn evaluation(&self) -> Evaluation {
+        self.evaluation
+    }
+}
+
+impl From<&GuessResponse> for RecordedGuess {
+    fn from(value: &GuessResponse) -> Self {
+        Self {
+            evaluation: *value.evaluation(),
+        }
+    }
+}
+
+impl Display for RecordedGuess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.evaluation.guess(),
+            self.evaluation.to_eval_string()
+        )
+    }
+}
+
+impl FromStr for RecordedGuess {
+    type Err = crate::error::Error;
+    /// Read a guess like `slate xxpxc`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (guess, evaluation) = s
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| bad_record(&format!("bad guess: {s}")))?;
+        RawGuess {
+            guess: guess.parse()?,
+            evaluation: evaluation.trim().to_string(),
+        }
+        .try_into()
+    }
+}
+
+/// how a [RecordedGuess] is serialized
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct RawGuess {
+    guess: Word,
+    evaluation: String,
+}
+
+impl From<RecordedGuess> for RawGuess {
+    fn from(value: RecordedGuess) -> Self {
+        Self {
+            guess: value.guess(),
+            evaluation: value.evaluation.to_eval_string(),
+        }
+    }
+}
+
+impl TryFrom<RawGuess> for RecordedGuess {
+    type Error = crate::error::Error;
+    fn try_from(value: RawGuess) -> Result<Self, Self::Error> {
+        Ok(Self {
+            evaluation: Evaluation::build(&value.guess, &value.evaluation)?,
+        })
+    }
+}
+
+/// Everything needed to rebuild a [Game]: how it was set up and every guess with it's evaluation
+///
+/// A record is made with [Game::record] and turned back into a [Game] with
+/// [replay](GameRecord::replay) or [to_game](GameRecord::to_game). The wordlist itself is not part
+/// of the record, only it's [name](WordList::name), so the same wordlist needs to be used for
+/// replaying.
+///
+/// ## Formats
+///
+/// With the `serde` feature, records can be (de)serialized, for example as JSON with
+/// [to_json](GameRecord::to_json) and [from_json](GameRecord::from_json).
+///
+/// ### Text form
+///
+/// The [Display] and [FromStr] implementations use a compact text form. The first line holds the
+/// setup of the game, with the name of the wordlist taking up the rest of the line. Every guess
+/// follows on it's own line, with the evaluation in the format of [Evaluation::build]:
+///
+/// ```text
+/// #wordle-analyzer v1 length=5 max_steps=6 hard_mode=false adversarial=false solution=crane wordlist=(builtin english)
+/// slate xxcxc
+/// crane ccccc
+/// ```
+///
+/// The `solution` is left out if the game did not know it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameRecord {
+    version: u32,
+    wordlist: String,
+    length: usize,
+    max_steps: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    hard_mode: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    adversarial: bool,
+    solution: Option<Word>,
+    guesses: Vec<RecordedGuess>,
+}
+
+impl GameRecord {
+    /// Record the current state of a [Game]
+    pub fn of<WL: WordList>(game: &Game<'_, WL>) -> Self {
+        Self {
+            version: RECORD_VERSION,
+            wordlist: game.wordlist().name().to_string(),
+            length: game.length(),
+            max_steps: game.max_steps(),
+            hard_mode: game.hard_mode(),
+            adversarial: game.adversarial(),
+            solution: game.solution().map(|s| s.0),
+            guesses: game.responses().iter().map(RecordedGuess::from).collect(),
+        }
+    }
+
+    /// Start replaying the record with the wordlist `wl`
+    ///
+    /// The returned [Replay] makes one guess after the other, see there.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the solution of the record is not in the wordlist, or the game can not be built.
+    pub fn replay<'wl, WL: WordList>(&self, wl: &'wl WL) -> WResult<Replay<'_, 'wl, WL>> {
+        if wl.name() != self.wordlist {
+            warn!(
+                "the record was made with the wordlist {}, replaying with {}",
+                self.wordlist,
+                wl.name()
+            );
+        }
+        let solution = match self.solution {
+            Some(solution) => Some(
+                wl.get_word(&solution)
+                    .ok_or(GameError::WordNotInWordlist(solution))?,
+            ),
+            None => None,
+        };
+        let game = GameBuilder::new(wl, false)
+            .length(self.length)
+            .max_steps(self.max_steps)
+            .hard_mode(self.hard_mode)
+            .adversarial(self.adversarial)
+            .solution(solution)
+            .build()?;
+        Ok(Replay {
+            record: self,
+            game,
+            next: 0,
+        })
+    }
+
+    /// Rebuild the [Game] with all guesses of the record made
+    ///
+    /// # Errors
+    ///
+    /// Fails if the record can not be [replayed](GameRecord::replay).
+    pub fn to_game<'wl, WL: WordList>(&self, wl: &'wl WL) -> WResult<Game<'wl, WL>> {
+        let mut replay = self.replay(wl)?;
+        for step in replay.by_ref() {
+            step?;
+        }
+        Ok(replay.into_game())
+    }
+
+    /// Serialize the record as JSON
+    ///
+    /// # Errors
+    ///
+    /// Fails if the record can not be serialized.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> WResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Load a record from JSON
+    ///
+    /// # Errors
+    ///
+    /// Fails if the JSON is not a valid record or the record has a newer
+    /// [version](RECORD_VERSION).
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> WResult<Self> {
+        let record: Self = serde_json::from_str(json)?;
+        check_version(record.version)?;
+        Ok(record)
+    }
+
+    /// the version of the format the record was written in
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// the name of the wordlist the game was played with
+    pub fn wordlist(&self) -> &str {
+        &self.wordlist
+    }
+
+    /// how many letters the words have
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// how many guesses the player had
+    pub fn max_steps(&self) -> usize {
+        self.max_steps
+    }
+
+    /// was the game played in hard mode?
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// was the game adversarial?
+    pub fn adversarial(&self) -> bool {
+        self.adversarial
+    }
+
+    /// the solution, if the game knew it
+    pub fn solution(&self) -> Option<Word> {
+        self.solution
+    }
+
+    /// all guesses that were made, in order
+    pub fn guesses(&self) -> &[RecordedGuess] {
+        &self.guesses
+    }
+}
+
+impl Display for GameRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{TEXT_MAGIC} v{} length={} max_steps={} hard_mode={} adversarial={}",
+            self.version, self.length, self.max_steps, self.hard_mode, self.adversarial
+        )?;
+        if let Some(solution) = self.solution {
+            write!(f, " solution={solution}")?;
+        }
+        write!(f, " wordlist={}", self.wordlist)?;
+        for guess in &self.guesses {
+            write!(f, "\n{guess}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = crate::error::Error;
+    /// Read the [text form](GameRecord#text-form) of a record
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+        let header = lines
+            .next()
+            .ok_or_else(|| bad_record("the record is empty"))?;
+        let (header, wordlist) = header
+            .split_once(" wordlist=")
+            .ok_or_else(|| bad_record("the wordlist is missing"))?;
+        let mut fields = header.split_whitespace();
+        if fields.next() != Some(TEXT_MAGIC) {
+            return Err(bad_record("this is not a game record"));
+        }
+        let version: u32 = fields
+            .next()
+            .and_then(|v| v.strip_prefix('v'))
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| bad_record("the version is missing"))?;
+        check_version(version)?;
+        let mut record = Self {
+            version,
+            wordlist: wordlist.to_string(),
+            length: 0,
+            max_steps: 0,
+            hard_mode: false,
+            adversarial: false,
+            solution: None,
+            guesses: Vec::new(),
+        };
+        for field in fields {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| bad_record(&format!("bad field: {field}")))?;
+            let bad_value = || bad_record(&format!("bad value for {key}: {value}"));
+            match key {
+                "length" => record.length = value.parse().map_err(|_| bad_value())?,
+                "max_steps" => record.max_steps = value.parse().map_err(|_| bad_value())?,
+                "hard_mode" => record.hard_mode = value.parse().map_err(|_| bad_value())?,
+                "adversarial" => record.adversarial = value.parse().map_err(|_| bad_value())?,
+                "solution" => record.solution = Some(value.parse()?),
+                // unknown fields might be added by later versions
+                _ => warn!("ignoring unknown field of game record: {field}"),
+            }
+        }
+        if record.length == 0 || record.max_steps == 0 {
+            return Err(bad_record("length and max_steps are required"));
+        }
+        for line in lines {
+            record.guesses.push(line.parse()?);
+        }
+        Ok(record)
+    }
+}
+
+/// A [GameRecord] that is being replayed, one guess at a time
+///
+/// Each call to [next](Iterator::next) makes the next recorded guess in the [Game] and returns
+/// the [GuessResponse]. If the game knows the solution, it evaluates the guesses itself, and the
+/// replay fails with [GameError::BadRecord] if that does not give the recorded evaluation.
+#[derive(Debug, Clone)]
+pub struct Replay<'r, 'wl, WL: WordList> {
+    record: &'r GameRecord,
+    game: Game<'wl, WL>,
+    next: usize,
+}
+
+impl<'wl, WL: WordList> Replay<'_, 'wl, WL> {
+    /// the game as far as it was replayed
+    pub fn game(&self) -> &Game<'wl, WL> {
+        &self.game
+    }
+
+    /// how many of the recorded guesses are left
+    pub fn remaining(&self) -> usize {
+        self.record.guesses.len() - self.next
+    }
+
+    /// Stop replaying and get the [Game] as far as it was replayed
+    pub fn into_game(self) -> Game<'wl, WL> {
+        self.game
+    }
+
+    fn step(&mut self, recorded: RecordedGuess) -> WResult<GuessResponse> {
+        let evaluation = recorded.evaluation();
+        let response = self.game.guess(&recorded.guess(), Some(evaluation))?;
+        if *response.evaluation() != evaluation {
+            return Err(bad_record(&format!(
+                "the game evaluates {} as {}, but {} was recorded",
+                recorded.guess(),
+                response.evaluation().to_eval_string(),
+                evaluation.to_eval_string()
+            )));
+        }
+        Ok(response)
+    }
+}
+
+impl<WL: WordList> Iterator for Replay<'_, '_, WL> {
+    type Item = WResult<GuessResponse>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let recorded = *self.record.guesses.get(self.next)?;
+        self.next += 1;
+        Some(self.step(recorded))
+    }
+}
+
+fn check_version(version: u32) -> WResult<()> {
+    if version > RECORD_VERSION {
+        return Err(bad_record(&format!(
+            "version {version} is newer than the supported version {RECORD_VERSION}"
+        )));
+    }
+    Ok(())
+}
+
+fn bad_record(msg: &str) -> crate::error::Error {
+    GameError::BadRecord(msg.to_string()).into()
+}
@@ -15,7 +15,7 @@ pub struct AtomicEvaluation {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-// #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GuessResponse {
     guess: Word,
     evaluation: Evaluation,
@@ -34,17 +34,27 @@ pub enum Status {
 
 impl From<char> for Status {
     fn from(value: char) -> Self {
-        let value = value.to_ascii_lowercase(); // let's not deal with unicode here, wordle is
-                                                // ASCII centric anyway
-        match value {
+        // the statuses are written with ASCII letters, any other char is no status
+        match value.to_ascii_lowercase() {
             'x' => Self::None,
-            'f' | 'e' => Self::Exists,
+            'f' | 'e' | 'p' => Self::Exists,
             'c' | 'm' => Self::Matched,
             _ => Self::None,
         }
     }
 }
 
+impl From<Status> for char {
+    /// The char for the [Status] in the format of [Evaluation::build]
+    fn from(value: Status) -> Self {
+        match value {
+            Status::None => 'x',
+            Status::Exists => 'p',
+            Status::Matched => 'c',
+        }
+    }
+}
+
 impl GuessResponse {
     pub(crate) fn new<WL: WordList>(guess: &Word, status: Evaluation, game: &Game<WL>) -> Self {
         let new = Self {
@@ -58,19 +68,19 @@ impl GuessResponse {
     }
 
     pub fn finished(&self) -> bool {
-        self.step() > self.max_steps() || self.won()
+        self.step() >= self.max_steps() || self.won()
     }
 
     pub fn won(&self) -> bool {
         let mut ok = true;
-        for i in self.evaluation.clone().into_iter() {
+        for i in self.evaluation.into_iter() {
             ok &= i.1 == Status::Matched
         }
         ok
     }
 
     pub fn solution(&self) -> Option<WordData> {
-        self.solution.clone()
+        self.solution
     }
 
     pub fn evaluation(&self) -> &Evaluation {
@@ -92,7 +102,7 @@ impl GuessResponse {
 
 impl Display for GuessResponse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for s in self.evaluation.clone().into_iter() {
+        for s in self.evaluation.into_iter() {
             write!(
                 f,
                 "{}",
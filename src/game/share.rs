@@ -0,0 +1,369 @@
+//! The emoji grid that is used to share the result of a wordle game
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::{GameError, WResult};
+use crate::wlist::word::{Word, WordData};
+use crate::wlist::WordList;
+
+use super::constraints::Constraints;
+use super::evaluation::{pattern, Evaluation, Pattern};
+use super::response::Status;
+use super::{Game, GameBuilder};
+
+/// the tile for [Status::Matched]
+pub const MATCHED_TILE: char = '🟩';
+/// the tile for [Status::Exists]
+pub const EXISTS_TILE: char = '🟨';
+/// the tile for [Status::None], as shared in dark mode
+pub const NONE_TILE: char = '⬛';
+
+/// The result of a game as it is shared, without the letters
+///
+/// ```text
+/// Wordle 1,234 4/6*
+///
+/// ⬛⬛🟨⬛⬛
+/// ⬛🟩⬛🟨⬛
+/// 🟩🟩⬛⬛🟩
+/// 🟩🟩🟩🟩🟩
+/// ```
+///
+/// The header names the puzzle, how many guesses were needed (`X` if the game was lost) and how
+/// many were allowed. The `*` marks games played in hard mode. Each row is one guess, with a
+/// tile for the [Status] of each letter.
+///
+/// A [Game] can be shared with [Game::share], the [Display] implementation renders the text. The
+/// text can be read with [FromStr], which also understands the light mode (`⬜`) and high contrast
+/// (`🟧` and `🟦`) tiles. As the letters are not part of the grid, the guesses can only be
+/// reconstructed when the solution is known, see [candidates](ShareGrid::candidates) and
+/// [to_game](ShareGrid::to_game).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ShareGrid {
+    /// the name of the puzzle, like the number of the daily wordle
+    puzzle: Option<String>,
+    length: usize,
+    max_steps: usize,
+    hard_mode: bool,
+    won: bool,
+    rows: Vec<Pattern>,
+}
+
+impl ShareGrid {
+    /// Share the result of a [Game]
+    pub fn of<WL: WordList>(game: &Game<'_, WL>) -> Self {
+        Self {
+            puzzle: None,
+            length: game.length(),
+            max_steps: game.max_steps(),
+            hard_mode: game.hard_mode(),
+            won: game.won(),
+            rows: game
+                .responses()
+                .iter()
+                .map(|r| r.evaluation().pattern())
+                .collect(),
+        }
+    }
+
+    /// Name the puzzle in the header, like the number of the daily wordle
+    pub fn with_puzzle(mut self, puzzle: impl Into<String>) -> Self {
+        self.puzzle = Some(puzzle.into());
+        self
+    }
+
+    /// the name of the puzzle, if it is known
+    pub fn puzzle(&self) -> Option<&str> {
+        self.puzzle.as_deref()
+    }
+
+    /// how many letters the words have
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// how many guesses were allowed
+    pub fn max_steps(&self) -> usize {
+        self.max_steps
+    }
+
+    /// was the game played in hard mode?
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// was the solution found?
+    pub fn won(&self) -> bool {
+        self.won
+    }
+
+    /// the [Pattern] of each guess
+    pub fn rows(&self) -> &[Pattern] {
+        &self.rows
+    }
+
+    /// Find the words that could have been guessed in each row, if `solution` was the solution
+    ///
+    /// The words of each row are sorted by their frequency, the most likely first. The rows are
+    /// looked at on their own, so in hard mode, not every combination of these words is a valid
+    /// game.
+    pub fn candidates<WL: WordList>(&self, wl: &WL, solution: &Word) -> Vec<Vec<WordData>> {
+        let mut words: Vec<WordData> = wl
+            .wordmap()
+            .iter()
+            .filter(|(w, _)| w.char_len() == self.length)
+            .map(|(w, f)| (*w, *f))
+            .collect();
+        words.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then(a.0.cmp(&b.0)));
+        self.rows
+            .iter()
+            .map(|row| {
+                words
+                    .iter()
+                    .filter(|w| pattern(solution, &w.0) == *row)
+                    .copied()
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Rebuild the most likely [Game] that has this grid, if `solution` was the solution
+    ///
+    /// For every row, the most likely word that gives the row is guessed. In hard mode, only
+    /// words that use all hints revealed by the guesses before are considered, so a more likely
+    /// word for an early row is given up if no word fits a later row after it.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [GameError::NoFeasibleGuess] for the furthest row that no word of the wordlist
+    /// fits, or with [GameError::WordNotInWordlist] if the solution is not in the wordlist.
+    pub fn to_game<'wl, WL: WordList>(
+        &self,
+        wl: &'wl WL,
+        solution: &Word,
+    ) -> WResult<Game<'wl, WL>> {
+        let solution_data = wl
+            .get_word(solution)
+            .ok_or(GameError::WordNotInWordlist(*solution))?;
+        let mut game = GameBuilder::new(wl, false)
+            .length(self.length)
+            .max_steps(self.max_steps)
+            .hard_mode(self.hard_mode)
+            .solution(Some(solution_data))
+            .build()?;
+        let candidates = self.candidates(wl, solution);
+        if let Some(idx) = candidates.iter().position(Vec::is_empty) {
+            return Err(GameError::NoFeasibleGuess(idx + 1).into());
+        }
+        let mut dead_ends = vec![Vec::new(); candidates.len()];
+        let mut deepest = 0;
+        if !fill_rows(&mut game, &candidates, &mut dead_ends, &mut deepest)? {
+            return Err(GameError::NoFeasibleGuess(deepest + 1).into());
+        }
+        Ok(game)
+    }
+
+    /// The [Evaluations](Evaluation) of the rows for a sequence of guesses
+    ///
+    /// # Errors
+    ///
+    /// Fails if there are not as many guesses as rows.
+    pub fn evaluations(&self, guesses: &[Word]) -> WResult<Vec<Evaluation>> {
+        if guesses.len() != self.rows.len() {
+            return Err(bad_grid(&format!(
+                "the grid has {} rows, but there are {} guesses",
+                self.rows.len(),
+                guesses.len()
+            )));
+        }
+        Ok(guesses
+            .iter()
+            .zip(&self.rows)
+            .map(|(g, p)| Evaluation::from_pattern(g, *p))
+            .collect())
+    }
+}
+
+impl Display for ShareGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Wordle ")?;
+        if let Some(puzzle) = &self.puzzle {
+            write!(f, "{puzzle} ")?;
+        }
+        if self.won {
+            write!(f, "{}", self.rows.len())?;
+        } else {
+            write!(f, "X")?;
+        }
+        write!(f, "/{}", self.max_steps)?;
+        if self.hard_mode {
+            write!(f, "*")?;
+        }
+        writeln!(f)?;
+        for row in &self.rows {
+            writeln!(f)?;
+            let mut p = *row;
+            for _ in 0..self.length {
+                let tile = match p % 3 {
+                    0 => NONE_TILE,
+                    1 => EXISTS_TILE,
+                    _ => MATCHED_TILE,
+                };
+                write!(f, "{tile}")?;
+                p /= 3;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ShareGrid {
+    type Err = crate::error::Error;
+    /// Read a shared grid, see [ShareGrid]
+    ///
+    /// Lines that are neither the header nor a row of tiles, like a link to the game, are
+    /// ignored. If there is no header, the game is assumed to be won if the last row is all
+    /// [matched](Status::Matched), with the default amount of guesses.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut grid = Self {
+            max_steps: crate::DEFAULT_MAX_STEPS,
+            ..Default::default()
+        };
+        let mut header: Option<Option<usize>> = None;
+        for line in s.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(rest) = header_rest(line) {
+                if header.is_none() {
+                    header = Some(grid.read_header(rest)?);
+                    continue;
+                }
+            }
+            let tiles: Option<Vec<Status>> = line
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '\u{fe0f}')
+                .map(tile_status)
+                .collect();
+            let Some(tiles) = tiles else {
+                continue;
+            };
+            if grid.length == 0 {
+                grid.length = tiles.len();
+            } else if tiles.len() != grid.length {
+                return Err(bad_grid("the rows have different lengths"));
+            }
+            let row = tiles.iter().rev().fold(0, |acc, s| acc * 3 + *s as Pattern);
+            grid.rows.push(row);
+        }
+        if grid.rows.is_empty() {
+            return Err(bad_grid("there are no rows"));
+        }
+        let solved = grid.rows.last() == Some(&(3_u32.pow(grid.length as u32) - 1));
+        match header {
+            Some(Some(score)) => {
+                if score != grid.rows.len() || !solved {
+                    return Err(bad_grid(&format!(
+                        "the score is {score}, but the game was not won with the last of {} rows",
+                        grid.rows.len()
+                    )));
+                }
+                grid.won = true;
+            }
+            Some(None) => grid.won = false,
+            None => grid.won = solved,
+        }
+        Ok(grid)
+    }
+}
+
+impl ShareGrid {
+    /// Read the header after "Wordle", returns the score if the game was won
+    fn read_header(&mut self, rest: &str) -> WResult<Option<usize>> {
+        let mut fields: Vec<&str> = rest.split_whitespace().collect();
+        let result = fields
+            .pop()
+            .ok_or_else(|| bad_grid("the header has no score"))?;
+        if !fields.is_empty() {
+            self.puzzle = Some(fields.join(" "));
+        }
+        let result = match result.strip_suffix('*') {
+            Some(r) => {
+                self.hard_mode = true;
+                r
+            }
+            None => result,
+        };
+        let (score, max) = result
+            .split_once('/')
+            .ok_or_else(|| bad_grid(&format!("bad score: {result}")))?;
+        self.max_steps = max
+            .parse()
+            .map_err(|_| bad_grid(&format!("bad score: {result}")))?;
+        let score = match score {
+            "X" | "x" => None,
+            n => Some(
+                n.parse()
+                    .map_err(|_| bad_grid(&format!("bad score: {result}")))?,
+            ),
+        };
+        Ok(score)
+    }
+}
+
+/// the part of a header line after "Wordle", [None] if the line is not a header
+///
+/// Other words that start with "Wordle", like "Wordlebot", do not make a header.
+fn header_rest(line: &str) -> Option<&str> {
+    match line.strip_prefix("Wordle")? {
+        "" => Some(""),
+        rest if rest.starts_with(char::is_whitespace) => Some(rest),
+        _ => None,
+    }
+}
+
+/// Guess a word for each row after the ones `game` already has, backtracking when a row has no
+/// allowed word
+///
+/// `dead_ends` has the [Constraints] of each row that were already found to lead nowhere, and
+/// `deepest` is set to the last row that was reached. Returns whether all rows could be filled.
+fn fill_rows<WL: WordList>(
+    game: &mut Game<'_, WL>,
+    candidates: &[Vec<WordData>],
+    dead_ends: &mut [Vec<Constraints>],
+    deepest: &mut usize,
+) -> WResult<bool> {
+    let row = game.responses().len();
+    if row == candidates.len() {
+        return Ok(true);
+    }
+    *deepest = (*deepest).max(row);
+    if dead_ends[row].contains(game.constraints()) {
+        return Ok(false);
+    }
+    for (word, _) in &candidates[row] {
+        if !game.allows(word) {
+            continue;
+        }
+        game.guess(word, None)?;
+        if fill_rows(game, candidates, dead_ends, deepest)? {
+            return Ok(true);
+        }
+        game.undo(1)?;
+    }
+    dead_ends[row].push(game.constraints().clone());
+    Ok(false)
+}
+
+/// the [Status] a tile of a shared grid stands for
+fn tile_status(tile: char) -> Option<Status> {
+    match tile {
+        // the high contrast mode uses orange and blue
+        '🟩' | '🟧' => Some(Status::Matched),
+        '🟨' | '🟦' => Some(Status::Exists),
+        '⬛' | '⬜' => Some(Status::None),
+        _ => None,
+    }
+}
+
+fn bad_grid(msg: &str) -> crate::error::Error {
+    GameError::BadShareGrid(msg.to_string()).into()
+}
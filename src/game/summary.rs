@@ -2,6 +2,7 @@ use crate::wlist::WordList;
 
 use super::Game;
 
+#[derive(Debug)]
 pub struct Summary<'wl, WL: WordList> {
     data: Vec<&'wl Game<'wl, WL>>,
 }
@@ -19,7 +20,7 @@ impl<'wl, WL: WordList> Summary<'wl, WL> {
     pub fn push(&mut self, game: &'wl Game<WL>) {
         self.data.push(game)
     }
-    pub fn pop(&mut self) -> Option<&Game<WL>> {
+    pub fn pop(&mut self) -> Option<&Game<'_, WL>> {
         self.data.pop()
     }
 }
@@ -4,6 +4,10 @@
 
 /// Default letters of a solution word
 pub const DEFAULT_WORD_LENGTH: usize = 5;
+/// The fewest letters the words of a game can have
+pub const MIN_WORD_LENGTH: usize = 3;
+/// The most letters the words of a game can have
+pub const MAX_WORD_LENGTH: usize = 12;
 /// Default amount of guesses per game
 pub const DEFAULT_MAX_STEPS: usize = 6;
 
@@ -0,0 +1,260 @@
+//! Review the guesses of a [Game] after it was played
+
+use std::fmt::Display;
+
+use libpt::log::trace;
+
+use crate::error::WResult;
+use crate::game::evaluation::{pattern, Evaluation};
+use crate::game::Game;
+use crate::wlist::word::{Frequency, Word};
+use crate::wlist::WordList;
+
+use super::entropy::{expected_information, normalized_weights};
+use super::{Session, Solver};
+
+/// What happened in a single step of a [Game], compared to what a [Solver] would have done
+///
+/// Information is measured in bits and weighted by the [priors](WordList::prior) of the
+/// candidates, like the [EntropySolver](super::EntropySolver) does. Each bit halves the
+/// candidates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepAnalysis {
+    step: usize,
+    evaluation: Evaluation,
+    candidates_before: usize,
+    candidates_after: usize,
+    information: f64,
+    expected_information: f64,
+    best_guess: Word,
+    best_expected_information: f64,
+}
+
+impl StepAnalysis {
+    /// the number of the step, starting at 1
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// the word that was guessed
+    pub fn guess(&self) -> Word {
+        self.evaluation.guess()
+    }
+
+    /// the [Evaluation] the guess got
+    pub fn evaluation(&self) -> &Evaluation {
+        &self.evaluation
+    }
+
+    /// how many words might have been the solution before the guess
+    pub fn candidates_before(&self) -> usize {
+        self.candidates_before
+    }
+
+    /// how many words might have been the solution after the guess
+    pub fn candidates_after(&self) -> usize {
+        self.candidates_after
+    }
+
+    /// the information the guess actually gave
+    pub fn information(&self) -> f64 {
+        self.information
+    }
+
+    /// the information the guess was expected to give before it's evaluation was known
+    pub fn expected_information(&self) -> f64 {
+        self.expected_information
+    }
+
+    /// the guess the [Solver] would have made
+    pub fn best_guess(&self) -> Word {
+        self.best_guess
+    }
+
+    /// the information the guess of the [Solver] was expected to give
+    pub fn best_expected_information(&self) -> f64 {
+        self.best_expected_information
+    }
+
+    /// was the guess the same as the one of the [Solver]?
+    pub fn was_best(&self) -> bool {
+        self.guess() == self.best_guess
+    }
+
+    /// How good the guess was, as the share of the expected information of the [Solver]'s guess
+    ///
+    /// This is 1 if the guess was as good as the one of the [Solver], and more than 1 if it was
+    /// better. If nothing was left to learn, this is 1 as well.
+    pub fn skill(&self) -> f64 {
+        if self.best_expected_information > 0.0 {
+            self.expected_information / self.best_expected_information
+        } else {
+            1.0
+        }
+    }
+
+    /// How lucky the guess was, the bits gained beyond what was expected
+    ///
+    /// Negative if the evaluation was less helpful than expected.
+    pub fn luck(&self) -> f64 {
+        self.information - self.expected_information
+    }
+}
+
+/// A review of every guess of a [Game]
+///
+/// For every step, the candidates before and after the guess are counted, and the information
+/// gained is compared with the information the guess of a [Solver] would have been expected to
+/// give in the same situation. See [StepAnalysis] for what is known about each step.
+///
+/// The [Game] does not need to know it's solution, so games played elsewhere can be reviewed
+/// after entering the guesses with their evaluations.
+///
+/// ## Example
+///
+/// ```
+/// use wordle_analyzer::game::Game;
+/// use wordle_analyzer::solve::analysis::Analysis;
+/// use wordle_analyzer::solve::{NaiveSolver, Solver};
+/// use wordle_analyzer::wlist::builtin::BuiltinWList;
+/// use wordle_analyzer::wlist::WordList;
+///
+/// let wl = BuiltinWList::default();
+/// let solver = NaiveSolver::build(&wl).unwrap();
+/// let mut game = Game::builder(&wl)
+///     .solution(wl.get_word(&"crane".parse().unwrap()))
+///     .build()
+///     .unwrap();
+/// game.guess(&"slate".parse().unwrap(), None).unwrap();
+/// game.guess(&"crane".parse().unwrap(), None).unwrap();
+///
+/// let analysis = Analysis::of(&solver, &game).unwrap();
+/// assert_eq!(analysis.steps().len(), 2);
+/// assert!(analysis.steps()[0].candidates_after() < analysis.steps()[0].candidates_before());
+/// assert_eq!(analysis.steps()[1].candidates_after(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    steps: Vec<StepAnalysis>,
+    won: bool,
+}
+
+impl Analysis {
+    /// Review the guesses of `game`, comparing them with the guesses of `solver`
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the [Solver] finds no guess for a step, or the game
+    /// can not be replayed.
+    pub fn of<'wl, WL: WordList, S: Solver<'wl, WL>>(
+        solver: &S,
+        game: &Game<'wl, WL>,
+    ) -> WResult<Self> {
+        let mut fresh = game.clone();
+        fresh.undo(game.responses().len())?;
+        let mut session = Session::new(solver, fresh);
+        let mut steps = Vec::with_capacity(game.responses().len());
+        for response in game.responses() {
+            let guess = *response.guess();
+            let observed = response.evaluation().pattern();
+            let before = session.candidates().to_vec();
+            let weights = normalized_weights(game.wordlist(), &before);
+            let best_guess = session.suggest()?;
+            let expected = expected_information(&guess, &before, &weights);
+            let best_expected = expected_information(&best_guess, &before, &weights);
+            let share: Frequency = before
+                .iter()
+                .zip(&weights)
+                .filter(|(c, _)| pattern(&c.0, &guess) == observed)
+                .map(|(_, w)| w)
+                .sum();
+
+            session.guess(&guess, Some(*response.evaluation()))?;
+            let after = session.candidates().len();
+            let information = if share > 0.0 {
+                -share.log2()
+            } else {
+                // the solution was not among the candidates, count the words instead
+                (before.len() as f64 / after.max(1) as f64).log2()
+            };
+            trace!(
+                "step {}: {information} bits, {expected} expected",
+                response.step()
+            );
+            steps.push(StepAnalysis {
+                step: response.step(),
+                evaluation: *response.evaluation(),
+                candidates_before: before.len(),
+                candidates_after: after,
+                information,
+                expected_information: expected,
+                best_guess,
+                best_expected_information: best_expected,
+            });
+        }
+        Ok(Self {
+            steps,
+            won: game.won(),
+        })
+    }
+
+    /// the review of each step, in the order the guesses were made
+    pub fn steps(&self) -> &[StepAnalysis] {
+        &self.steps
+    }
+
+    /// was the game won?
+    pub fn won(&self) -> bool {
+        self.won
+    }
+
+    /// the information gained by all guesses together
+    pub fn total_information(&self) -> f64 {
+        self.steps.iter().map(|s| s.information).sum()
+    }
+
+    /// the average [skill](StepAnalysis::skill) of the guesses
+    pub fn skill(&self) -> f64 {
+        if self.steps.is_empty() {
+            return 1.0;
+        }
+        self.steps.iter().map(|s| s.skill()).sum::<f64>() / self.steps.len() as f64
+    }
+
+    /// the [luck](StepAnalysis::luck) of all guesses together
+    pub fn luck(&self) -> f64 {
+        self.steps.iter().map(|s| s.luck()).sum()
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<5} {:<8} {:>15} {:>14} {:>16} {:>6} {:>6}",
+            "step", "guess", "candidates", "bits (exp.)", "best (exp.)", "skill", "luck"
+        )?;
+        for s in &self.steps {
+            writeln!(
+                f,
+                "{:<5} {:<8} {:>15} {:>14} {:>16} {:>5.0}% {:>+6.2}",
+                format!("{}.", s.step),
+                s.guess(),
+                format!("{} -> {}", s.candidates_before, s.candidates_after),
+                format!("{:.2} ({:.2})", s.information, s.expected_information),
+                format!("{} ({:.2})", s.best_guess, s.best_expected_information),
+                s.skill() * 100.0,
+                s.luck()
+            )?;
+        }
+        write!(
+            f,
+            "{} with {} guesses, {:.2} bits gained, skill {:.0}%, luck {:+.2} bits",
+            if self.won { "won" } else { "not won" },
+            self.steps.len(),
+            self.total_information(),
+            self.skill() * 100.0,
+            self.luck()
+        )
+    }
+}
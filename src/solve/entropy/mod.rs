@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use libpt::log::{debug, info, trace};
+use rayon::prelude::*;
+
+use crate::error::{SolverError, WResult};
+use crate::game::evaluation::{pattern, Pattern};
+use crate::game::multi::MultiGame;
+use crate::game::Game;
+use crate::wlist::word::{Frequency, Word, WordData};
+use crate::wlist::WordList;
+
+use super::{AnyBuiltinSolver, Solver};
+
+/// Buckets for patterns are kept in a [Vec] if there are at most this many possible patterns,
+/// otherwise a [HashMap] is used.
+const MAX_DENSE_PATTERNS: usize = 1 << 16;
+
+/// A [Solver] that maximizes the expected information of each guess
+///
+/// ## Algorithm
+///
+/// * Find all solutions that are still possible with the responses of the game so far
+/// * For every word in the wordlist, group these candidates by the [Pattern] the word would get
+///   if the candidate was the solution. The probability of each group is the sum of the
+///   [priors](WordList::prior) of the candidates in it.
+/// * Guess the word for which these groups have the highest entropy, which is the amount of
+///   information in bits we expect to gain by making that guess. If multiple words are equally
+///   good, prefer the one that might be the solution.
+///
+/// For a [MultiGame], the information is summed up over all boards that are not solved yet.
+///
+/// In [hard mode](Game::hard_mode), only words that use all revealed hints are considered.
+///
+/// The first guess is always the same for a wordlist, word length and mode, so it is only
+/// calculated once per solver.
+///
+/// If the game has a [PatternMatrix](crate::game::matrix::PatternMatrix), the patterns are
+/// looked up instead of calculated, which is a lot faster.
+#[derive(Debug, Clone)]
+pub struct EntropySolver<'wl, WL> {
+    wl: &'wl WL,
+    /// the first guess for each word length and if it's hard mode
+    openings: Arc<Mutex<HashMap<(usize, bool), Word>>>,
+}
+
+impl<'wl, WL: WordList> EntropySolver<'wl, WL> {
+    /// the cached first guess for the game, if it has no responses yet
+    fn opening(&self, game: &Game<'wl, WL>) -> Option<Word> {
+        if !game.responses().is_empty() {
+            return None;
+        }
+        self.openings
+            .lock()
+            .unwrap()
+            .get(&(game.length(), game.hard_mode()))
+            .copied()
+    }
+
+    /// Select the guess for the `candidates`, fails if there are none
+    fn guess_among(&self, game: &Game<'wl, WL>, candidates: &[WordData]) -> WResult<Word> {
+        debug!("{} candidates left", candidates.len());
+        if candidates.is_empty() {
+            return Err(SolverError::NoMatches(game.solution().cloned()).into());
+        }
+        self.best_guess(candidates, game)
+    }
+
+    /// Select the guess that maximizes the expected information for the `candidates`
+    fn best_guess(&self, candidates: &[WordData], game: &Game<'wl, WL>) -> WResult<Word> {
+        // probably the solution, no need to waste a step
+        if candidates.len() <= 2 {
+            return most_likely(self.wl, candidates)
+                .ok_or(SolverError::NoMatches(game.solution().cloned()).into());
+        }
+        self.best_guess_for_boards(&[(game, candidates)])
+            .ok_or(SolverError::NoMatches(game.solution().cloned()).into())
+    }
+
+    /// Select the guess that maximizes the expected information summed over multiple boards,
+    /// each with it's own candidates
+    ///
+    /// Only words that may be guessed on all boards are considered.
+    fn best_guess_for_boards(&self, boards: &[(&Game<'wl, WL>, &[WordData])]) -> Option<Word> {
+        let (first, _) = boards.first()?;
+        let length = first.length();
+        let patterns = first.patterns();
+        let made_guesses = first.made_guesses();
+        let weights: Vec<Vec<Frequency>> = boards
+            .iter()
+            .map(|(_, candidates)| normalized_weights(self.wl, candidates))
+            .collect();
+        // the columns of the candidates, if the patterns were precomputed
+        let columns: Option<Vec<Vec<Option<usize>>>> = patterns.map(|m| {
+            boards
+                .iter()
+                .map(|(_, candidates)| candidates.iter().map(|c| m.solution_index(&c.0)).collect())
+                .collect()
+        });
+        let best = self
+            .wl
+            .wordmap()
+            .keys()
+            .par_bridge()
+            .filter(|w| {
+                w.char_len() == length
+                    && !made_guesses.contains(w)
+                    && boards.iter().all(|(board, _)| board.allows(w))
+            })
+            .map(|guess| {
+                let row = patterns.and_then(|m| Some(m.row(m.guess_index(guess)?)));
+                let mut info = 0.0;
+                let mut chance = 0.0;
+                for (idx, (_, candidates)) in boards.iter().enumerate() {
+                    let weights = &weights[idx];
+                    info += match (row, &columns) {
+                        (Some(row), Some(columns)) => {
+                            let cols = &columns[idx];
+                            information_of(guess.char_len(), weights, |i| match cols[i] {
+                                Some(col) => row[col] as Pattern,
+                                None => pattern(&candidates[i].0, guess),
+                            })
+                        }
+                        _ => expected_information(guess, candidates, weights),
+                    };
+                    chance += candidates
+                        .iter()
+                        .zip(weights)
+                        .find(|(c, _)| c.0 == *guess)
+                        .map(|(_, p)| *p)
+                        .unwrap_or(0.0);
+                }
+                (guess, info, chance)
+            })
+            .max_by(|a, b| {
+                a.1.partial_cmp(&b.1)
+                    .unwrap()
+                    .then(a.2.partial_cmp(&b.2).unwrap())
+                    // the order of the wordmap is random, make the result reproducible
+                    .then(b.0.cmp(a.0))
+            });
+        best.map(|(guess, info, _)| {
+            trace!("best guess is {guess} with {info} bits");
+            *guess
+        })
+    }
+}
+
+impl<'wl, WL: WordList> Solver<'wl, WL> for EntropySolver<'wl, WL> {
+    fn build(wordlist: &'wl WL) -> WResult<Self> {
+        info!("using entropy solver");
+        Ok(Self {
+            wl: wordlist,
+            openings: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+    /// Guess the word that gives the most information for the given game
+    ///
+    /// See [EntropySolver] for details on the algorithm.
+    fn guess_for(&self, game: &Game<'wl, WL>) -> WResult<Word> {
+        if let Some(opening) = self.opening(game) {
+            return Ok(opening);
+        }
+        let guess = self.guess_among(game, &super::candidates(game))?;
+        // without responses, the candidates are all solutions, so every game starts like this
+        if game.responses().is_empty() {
+            self.openings
+                .lock()
+                .unwrap()
+                .insert((game.length(), game.hard_mode()), guess);
+        }
+        Ok(guess)
+    }
+    /// Like [guess_for](Solver::guess_for), but with the candidates known already
+    ///
+    /// The cached first guess is only used if the candidates are all solutions.
+    fn guess_for_candidates(&self, game: &Game<'wl, WL>, candidates: &[WordData]) -> WResult<Word> {
+        // the candidates of a game without responses are all solutions, fewer were narrowed down
+        if game.responses().is_empty() && candidates.len() == super::candidates(game).len() {
+            return self.guess_for(game);
+        }
+        self.guess_among(game, candidates)
+    }
+    /// Guess the word that gives the most information for all boards together
+    ///
+    /// If the solution of a board is known, it is guessed right away.
+    fn guess_for_multi(&self, game: &MultiGame<'wl, WL>) -> WResult<Word> {
+        // all boards have the same candidates, so the best opening is the same as for one board
+        if game.step() == 0 {
+            return self.guess_for(&game.boards()[0]);
+        }
+        let boards: Vec<(&Game<'wl, WL>, Vec<WordData>)> = game
+            .unsolved()
+            .map(|board| (board, super::candidates(board)))
+            .collect();
+        if let Some((_, candidates)) = boards.iter().find(|(_, c)| c.len() == 1) {
+            return Ok(candidates[0].0);
+        }
+        match boards.as_slice() {
+            [] => Err(SolverError::NoMatches(None).into()),
+            [(board, _)] => self.guess_for(board),
+            _ => {
+                let boards: Vec<(&Game<'wl, WL>, &[WordData])> =
+                    boards.iter().map(|(b, c)| (*b, c.as_slice())).collect();
+                self.best_guess_for_boards(&boards)
+                    .ok_or(SolverError::NoMatches(None).into())
+            }
+        }
+    }
+}
+
+impl<'wl, WL: WordList> From<EntropySolver<'wl, WL>> for AnyBuiltinSolver<'wl, WL> {
+    fn from(value: EntropySolver<'wl, WL>) -> Self {
+        Self::Entropy(value)
+    }
+}
+
+/// Calculate the expected information in bits of guessing `guess`
+///
+/// `candidates` are the words that might be the solution, `weights` are their probabilities, in
+/// the same order.
+pub(crate) fn expected_information(
+    guess: &Word,
+    candidates: &[WordData],
+    weights: &[Frequency],
+) -> f64 {
+    information_of(guess.char_len(), weights, |i| {
+        pattern(&candidates[i].0, guess)
+    })
+}
+
+/// Calculate the expected information in bits of a guess with `length` letters, where
+/// `pattern_of(i)` is the [Pattern] the guess gets if the `i`th candidate is the solution
+fn information_of(
+    length: usize,
+    weights: &[Frequency],
+    pattern_of: impl Fn(usize) -> Pattern,
+) -> f64 {
+    let n_patterns = 3_usize.saturating_pow(length as u32);
+    let buckets: Vec<Frequency> = if n_patterns <= MAX_DENSE_PATTERNS {
+        let mut buckets: Vec<Frequency> = vec![0.0; n_patterns];
+        for (i, weight) in weights.iter().enumerate() {
+            buckets[pattern_of(i) as usize] += weight;
+        }
+        buckets
+    } else {
+        let mut buckets: HashMap<Pattern, Frequency> = HashMap::new();
+        for (i, weight) in weights.iter().enumerate() {
+            *buckets.entry(pattern_of(i)).or_default() += weight;
+        }
+        buckets.into_values().collect()
+    };
+    buckets
+        .into_iter()
+        .filter(|p| *p > 0.0)
+        .map(|p| -p * p.log2())
+        .sum()
+}
+
+/// Get the probability of each candidate to be the solution, based on it's
+/// [prior](WordList::prior)
+///
+/// If no candidate is plausible at all, they are all equally likely.
+pub(crate) fn normalized_weights<WL: WordList>(wl: &WL, candidates: &[WordData]) -> Vec<Frequency> {
+    let priors: Vec<Frequency> = candidates.iter().map(|c| wl.prior(&c.0)).collect();
+    let total: Frequency = priors.iter().sum();
+    if total > 0.0 {
+        priors.iter().map(|p| p / total).collect()
+    } else {
+        vec![1.0 / candidates.len() as Frequency; candidates.len()]
+    }
+}
+
+/// the candidate with the highest [prior](WordList::prior), the most frequent of those
+fn most_likely<WL: WordList>(wl: &WL, candidates: &[WordData]) -> Option<Word> {
+    candidates
+        .iter()
+        .map(|c| (wl.prior(&c.0), c))
+        .max_by(|(pa, a), (pb, b)| {
+            pa.partial_cmp(pb)
+                .unwrap()
+                .then(a.1.partial_cmp(&b.1).unwrap())
+                .then(b.0.cmp(&a.0))
+        })
+        .map(|(_, c)| c.0)
+}
@@ -1,14 +1,26 @@
 use std::{fmt::Display, str::FromStr};
 
+use libpt::log::debug;
+
 use crate::{
     error::{SolverError, WResult},
-    game::{response::*, Game},
+    game::{multi::MultiGame, response::*, Game},
     wlist::{
         word::{Word, WordData},
         WordList,
     },
 };
 
+pub mod session;
+pub use session::Session;
+
+#[cfg(feature = "builtin")]
+pub mod analysis;
+
+#[cfg(feature = "builtin")]
+pub mod entropy;
+#[cfg(feature = "builtin")]
+pub use entropy::EntropySolver;
 #[cfg(feature = "builtin")]
 pub mod naive;
 #[cfg(feature = "builtin")]
@@ -17,6 +29,10 @@ pub use naive::NaiveSolver;
 pub mod stupid;
 #[cfg(feature = "builtin")]
 pub use stupid::StupidSolver;
+#[cfg(feature = "builtin")]
+pub mod tree;
+#[cfg(feature = "builtin")]
+pub use tree::DecisionTreeSolver;
 
 /// Trait for any datatype that can solve [Games][Game].
 ///
@@ -31,20 +47,47 @@ pub use stupid::StupidSolver;
 /// This [crate] implements a few builtin [Solvers][Solver]:
 ///
 /// * [Naive](NaiveSolver) - Keep the found letters and use letters that are confirmed to be
-///                          contained. This is probably the closest thing to how a human would
-///                          play wordle.
+///   contained. This is probably the closest thing to how a human would play wordle.
 /// * [Stupid](StupidSolver) - Guesses random words that have not been guessed yet.
+/// * [Entropy](EntropySolver) - Guesses the word that gives the most information about the
+///   solution, weighted by how common the possible solutions are.
+/// * [DecisionTree](DecisionTreeSolver) - Precomputes a strategy for all solutions of the wordlist
+///   that needs as few guesses as possible.
 ///
 /// If you want to have the user select a model, create an enum with it's variants containing your
 /// [Solvers][Solver] and have this enum implement [Solver], see [AnyBuiltinSolver].
 pub trait Solver<'wl, WL: WordList>: Clone + std::fmt::Debug + Sized + Sync {
     /// Build and initialize a [Solver]
     fn build(wordlist: &'wl WL) -> WResult<Self>;
+    /// A short name of the [Solver], used to tell solvers apart in
+    /// [reports](crate::bench::report::Report)
+    ///
+    /// The default is the name of the type without it's path and generics.
+    fn name(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name).to_string()
+    }
     /// Calculate the next guess for a [Game]
     ///
     /// Each [Solver] needs to implement this method themselves, many other methods rely on this to
     /// play the [Game], such as [play](Solver::play) or [solve](Solver::solve).
     fn guess_for(&self, game: &Game<'wl, WL>) -> WResult<Word>;
+    /// Calculate the next guess for a [Game] when the words that might be the solution are
+    /// already known
+    ///
+    /// A [Session] keeps track of the `candidates` across guesses and uses this to ask for the
+    /// next guess. [Solvers](Solver) that would otherwise search for the candidates themselves
+    /// should implement this to save the work, the default just calls
+    /// [guess_for](Solver::guess_for).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if no guess can be found.
+    fn guess_for_candidates(&self, game: &Game<'wl, WL>, candidates: &[WordData]) -> WResult<Word> {
+        let _ = candidates;
+        self.guess_for(game)
+    }
     /// Make a singular step for a [Game]
     ///
     /// # Errors
@@ -55,19 +98,18 @@ pub trait Solver<'wl, WL: WordList>: Clone + std::fmt::Debug + Sized + Sync {
     }
     /// Play a [Game] and return the last [GuessResponse].
     ///
+    /// The game is played in a [Session], so the candidates are narrowed down with each guess
+    /// instead of being searched from scratch.
+    ///
     /// # Errors
     ///
-    /// This function will return an error if [make_a_move](Solver::make_a_move) fails.
+    /// This function will return an error if [Session::make_a_move] fails.
     fn play(&self, game: &mut Game<'wl, WL>) -> WResult<GuessResponse> {
         // TODO: check if the game is finished already and return an Err if so
-        let mut resp: GuessResponse;
-        loop {
-            resp = self.make_a_move(game)?;
-            if game.finished() {
-                break;
-            }
-        }
-        Ok(resp)
+        let mut session = Session::new(self, game.clone());
+        let resp = session.play();
+        *game = session.into_game();
+        resp
     }
     /// Play a [Game] and return the last [GuessResponse].
     ///
@@ -75,16 +117,9 @@ pub trait Solver<'wl, WL: WordList>: Clone + std::fmt::Debug + Sized + Sync {
     ///
     /// # Errors
     ///
-    /// This function will return an error if [make_a_move](Solver::make_a_move) fails.
-    fn play_owned(&self, mut game: Game<'wl, WL>) -> WResult<GuessResponse> {
-        let mut resp: GuessResponse;
-        loop {
-            resp = self.make_a_move(&mut game)?;
-            if game.finished() {
-                break;
-            }
-        }
-        Ok(resp)
+    /// This function will return an error if [Session::make_a_move] fails.
+    fn play_owned(&self, game: Game<'wl, WL>) -> WResult<GuessResponse> {
+        Session::new(self, game).play()
     }
     /// Play a [Game] and return the solution if one was found
     ///
@@ -97,6 +132,43 @@ pub trait Solver<'wl, WL: WordList>: Clone + std::fmt::Debug + Sized + Sync {
         let mut game = game.clone();
         Ok(self.play(&mut game)?.solution())
     }
+    /// Calculate the next guess for a [MultiGame]
+    ///
+    /// If the solution of a board is known, it is guessed right away. Otherwise, this plays for the
+    /// first board that is not solved yet. [Solvers](Solver) that can do better by looking at all
+    /// boards should implement this themselves.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if [guess_for](Solver::guess_for) fails.
+    fn guess_for_multi(&self, game: &MultiGame<'wl, WL>) -> WResult<Word> {
+        let mut first = None;
+        for board in game.unsolved() {
+            let candidates = candidates(board);
+            if candidates.len() == 1 {
+                return Ok(candidates[0].0);
+            }
+            first = first.or(Some(board));
+        }
+        match first {
+            Some(board) => self.guess_for(board),
+            None => Err(SolverError::NoMatches(None).into()),
+        }
+    }
+    /// Play a [MultiGame] until it is finished
+    ///
+    /// Use [won](MultiGame::won) to see if all boards were solved.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if [guess_for_multi](Solver::guess_for_multi) fails or
+    /// the guess can not be made.
+    fn play_multi(&self, game: &mut MultiGame<'wl, WL>) -> WResult<()> {
+        while !game.finished() {
+            game.guess(&self.guess_for_multi(game)?)?;
+        }
+        Ok(())
+    }
     /// Box the [Solver]
     ///
     /// Returns a [Box] containing the [Solver].
@@ -109,6 +181,8 @@ pub trait Solver<'wl, WL: WordList>: Clone + std::fmt::Debug + Sized + Sync {
 pub enum AnyBuiltinSolver<'wl, WL: WordList> {
     Naive(NaiveSolver<'wl, WL>),
     Stupid(StupidSolver<'wl, WL>),
+    Entropy(EntropySolver<'wl, WL>),
+    DecisionTree(DecisionTreeSolver<'wl, WL>),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -116,12 +190,16 @@ pub enum BuiltinSolverNames {
     #[default]
     Naive,
     Stupid,
+    Entropy,
+    DecisionTree,
 }
 impl BuiltinSolverNames {
     pub fn to_solver<'wl, WL: WordList>(&self, wl: &'wl WL) -> AnyBuiltinSolver<'wl, WL> {
         match self {
             Self::Naive => NaiveSolver::build(wl).unwrap().into(),
             Self::Stupid => StupidSolver::build(wl).unwrap().into(),
+            Self::Entropy => EntropySolver::build(wl).unwrap().into(),
+            Self::DecisionTree => DecisionTreeSolver::build(wl).unwrap().into(),
         }
     }
 }
@@ -132,6 +210,8 @@ impl FromStr for BuiltinSolverNames {
         match s.to_lowercase().as_str() {
             "naive" => Ok(Self::Naive),
             "stupid" => Ok(Self::Stupid),
+            "entropy" => Ok(Self::Entropy),
+            "tree" | "decisiontree" => Ok(Self::DecisionTree),
             _ => Err(Self::Err::UnknownBuiltinSolver),
         }
     }
@@ -147,10 +227,49 @@ impl<'wl, WL: WordList> Solver<'wl, WL> for AnyBuiltinSolver<'wl, WL> {
     fn build(wordlist: &'wl WL) -> WResult<Self> {
         Ok(Self::Naive(NaiveSolver::build(wordlist)?))
     }
+    fn name(&self) -> String {
+        match self {
+            Self::Naive(solver) => solver.name(),
+            Self::Stupid(solver) => solver.name(),
+            Self::Entropy(solver) => solver.name(),
+            Self::DecisionTree(solver) => solver.name(),
+        }
+    }
     fn guess_for(&self, game: &Game<'wl, WL>) -> WResult<Word> {
         Ok(match self {
             Self::Naive(solver) => solver.guess_for(game)?,
             Self::Stupid(solver) => solver.guess_for(game)?,
+            Self::Entropy(solver) => solver.guess_for(game)?,
+            Self::DecisionTree(solver) => solver.guess_for(game)?,
+        })
+    }
+    fn guess_for_candidates(&self, game: &Game<'wl, WL>, candidates: &[WordData]) -> WResult<Word> {
+        Ok(match self {
+            Self::Naive(solver) => solver.guess_for_candidates(game, candidates)?,
+            Self::Stupid(solver) => solver.guess_for_candidates(game, candidates)?,
+            Self::Entropy(solver) => solver.guess_for_candidates(game, candidates)?,
+            Self::DecisionTree(solver) => solver.guess_for_candidates(game, candidates)?,
+        })
+    }
+    fn guess_for_multi(&self, game: &MultiGame<'wl, WL>) -> WResult<Word> {
+        Ok(match self {
+            Self::Naive(solver) => solver.guess_for_multi(game)?,
+            Self::Stupid(solver) => solver.guess_for_multi(game)?,
+            Self::Entropy(solver) => solver.guess_for_multi(game)?,
+            Self::DecisionTree(solver) => solver.guess_for_multi(game)?,
         })
     }
 }
+
+/// Get all solutions that are still possible for the given game
+///
+/// If none of the [solutions](WordList::solutions) match, all words of the wordlist are
+/// considered, as the game may be played with a solution that is not considered common.
+pub(crate) fn candidates<WL: WordList>(game: &Game<'_, WL>) -> Vec<WordData> {
+    let candidates = game.wordlist().solutions_matching(game.constraints());
+    if !candidates.is_empty() {
+        return candidates;
+    }
+    debug!("no common solution matches, considering the whole wordlist");
+    game.wordlist().words_matching(game.constraints())
+}
@@ -0,0 +1,162 @@
+//! Play a [Game] with a [Solver] while keeping track of the remaining candidates
+
+use libpt::log::{debug, trace};
+
+use crate::error::WResult;
+use crate::game::evaluation::Evaluation;
+use crate::game::response::GuessResponse;
+use crate::game::Game;
+use crate::wlist::word::{Word, WordData};
+use crate::wlist::WordList;
+
+use super::Solver;
+
+/// A [Game] together with the [Solver] that plays it
+///
+/// The session remembers the words that might still be the solution after each guess. When a
+/// new [GuessResponse] comes in, only these candidates are checked against it, instead of going
+/// over the whole wordlist and all responses again, and the [Solver] gets them with
+/// [guess_for_candidates](Solver::guess_for_candidates). [Undoing](Session::undo) guesses rewinds
+/// the candidates as well.
+///
+/// The candidates are the [solutions](WordList::solutions) that fit the
+/// [constraints](Game::constraints) of the game. If no solution fits anymore, all words of the
+/// wordlist are considered, as the game may be played with a solution that is not common.
+///
+/// ## Example
+///
+/// ```
+/// use wordle_analyzer::game::Game;
+/// use wordle_analyzer::solve::{NaiveSolver, Session, Solver};
+/// use wordle_analyzer::wlist::builtin::BuiltinWList;
+/// use wordle_analyzer::wlist::WordList;
+///
+/// let wl = BuiltinWList::default();
+/// let solver = NaiveSolver::build(&wl).unwrap();
+/// let game = Game::builder(&wl)
+///     .solution(wl.get_word(&"crane".parse().unwrap()))
+///     .build()
+///     .unwrap();
+/// let mut session = Session::new(&solver, game);
+/// let response = session.play().unwrap();
+/// assert!(response.won());
+/// assert_eq!(session.candidates().len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Session<'s, 'wl, WL: WordList, S: Solver<'wl, WL>> {
+    solver: &'s S,
+    game: Game<'wl, WL>,
+    /// the candidates before the first guess of the session and after every guess since then
+    history: Vec<Vec<WordData>>,
+}
+
+impl<'s, 'wl, WL: WordList, S: Solver<'wl, WL>> Session<'s, 'wl, WL, S> {
+    /// Start a session for `game`, which may already have responses
+    pub fn new(solver: &'s S, game: Game<'wl, WL>) -> Self {
+        let candidates = super::candidates(&game);
+        Self {
+            solver,
+            game,
+            history: vec![candidates],
+        }
+    }
+
+    /// Continue with another [Game], forgetting everything about the current one
+    pub fn restart(&mut self, game: Game<'wl, WL>) {
+        self.history = vec![super::candidates(&game)];
+        self.game = game;
+    }
+
+    /// Ask the [Solver] what to guess next
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the [Solver] finds no guess.
+    pub fn suggest(&self) -> WResult<Word> {
+        self.solver
+            .guess_for_candidates(&self.game, self.candidates())
+    }
+
+    /// Make a guess in the [Game] and narrow down the candidates with the response
+    ///
+    /// See [Game::guess] for the meaning of `eval`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the [Game] does not accept the guess.
+    pub fn guess(&mut self, guess: &Word, eval: Option<Evaluation>) -> WResult<GuessResponse> {
+        let response = self.game.guess(guess, eval)?;
+        let mut candidates = self.candidates().to_vec();
+        self.game.constraints().retain(&mut candidates);
+        if candidates.is_empty() {
+            debug!("no common solution matches, considering the whole wordlist");
+            candidates = self.game.wordlist().words_matching(self.game.constraints());
+        }
+        trace!("{} candidates left", candidates.len());
+        self.history.push(candidates);
+        Ok(response)
+    }
+
+    /// Make the guess the [Solver] [suggests](Session::suggest)
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is no suggestion or it can not be guessed.
+    pub fn make_a_move(&mut self) -> WResult<GuessResponse> {
+        let guess = self.suggest()?;
+        self.guess(&guess, None)
+    }
+
+    /// Play until the [Game] is finished and return the last [GuessResponse]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if [make_a_move](Session::make_a_move) fails.
+    pub fn play(&mut self) -> WResult<GuessResponse> {
+        loop {
+            let response = self.make_a_move()?;
+            if self.game.finished() {
+                return Ok(response);
+            }
+        }
+    }
+
+    /// Discard the last n guesses and go back to the candidates from before them
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if [Game::undo] fails.
+    pub fn undo(&mut self, n: usize) -> WResult<()> {
+        let n = n.min(self.game.responses().len());
+        self.game.undo(n)?;
+        if n < self.history.len() {
+            self.history.truncate(self.history.len() - n);
+        } else {
+            // the guesses were made before the session started
+            self.history = vec![super::candidates(&self.game)];
+        }
+        Ok(())
+    }
+
+    /// the words that might still be the solution, the most likely first
+    pub fn candidates(&self) -> &[WordData] {
+        self.history
+            .last()
+            .expect("a session always knows it's candidates")
+    }
+
+    /// the [Game] that is played
+    pub fn game(&self) -> &Game<'wl, WL> {
+        &self.game
+    }
+
+    /// the [Solver] that plays the [Game]
+    pub fn solver(&self) -> &'s S {
+        self.solver
+    }
+
+    /// End the session and get the [Game] back
+    pub fn into_game(self) -> Game<'wl, WL> {
+        self.game
+    }
+}
@@ -1,6 +1,7 @@
 use libpt::log::info;
+use rand::seq::IteratorRandom;
 
-use crate::error::WResult;
+use crate::error::{SolverError, WResult};
 use crate::wlist::word::Word;
 use crate::wlist::WordList;
 
@@ -17,7 +18,17 @@ impl<'wl, WL: WordList> Solver<'wl, WL> for StupidSolver<'wl, WL> {
         Ok(Self { wl: wordlist })
     }
     fn guess_for(&self, game: &crate::game::Game<WL>) -> WResult<Word> {
-        Ok(self.wl.rand_word().0)
+        if !game.hard_mode() {
+            return Ok(self.wl.rand_word().0);
+        }
+        // most words break the rules of hard mode, so pick from the ones that don't
+        self.wl
+            .wordmap()
+            .keys()
+            .filter(|w| game.allows(w))
+            .choose(&mut rand::thread_rng())
+            .cloned()
+            .ok_or(SolverError::NoMatches(game.solution().cloned()).into())
     }
 }
 
@@ -0,0 +1,852 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use libpt::log::{debug, info, trace, warn};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GameError, SolverError, WResult};
+use crate::game::evaluation::{hard_mode_violation, pattern, Pattern};
+use crate::game::matrix::PatternMatrix;
+use crate::game::response::GuessResponse;
+use crate::game::Game;
+use crate::wlist::word::Word;
+use crate::wlist::WordList;
+use crate::MAX_WORD_LENGTH;
+
+use super::{AnyBuiltinSolver, Solver};
+
+/// A good amount of guesses to try in each node of a [DecisionTree] when speed matters
+///
+/// The bundled wordlists are much too large to search all guesses, which is what a
+/// [TreeConfig] does by default. With this [breadth](TreeConfig::breadth), their trees are built
+/// in reasonable time, but they are no longer guaranteed to be optimal.
+pub const DEFAULT_BREADTH: usize = 8;
+
+/// What a [DecisionTree] should optimize for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Objective {
+    /// Minimize the average amount of guesses over all solutions
+    #[default]
+    Expected,
+    /// Minimize the amount of guesses needed for the hardest solution
+    WorstCase,
+}
+
+impl Display for Objective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Expected => write!(f, "expected"),
+            Self::WorstCase => write!(f, "worst-case"),
+        }
+    }
+}
+
+/// Configuration for building a [DecisionTree]
+///
+/// The default optimizes the [expected](Objective::Expected) guesses and searches all guesses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TreeConfig {
+    /// What the tree should optimize for
+    pub objective: Objective,
+    /// How many guesses should be tried for each node
+    ///
+    /// The guesses are ranked by how well they split the remaining solutions, and only the best
+    /// `breadth` of them are searched. If this is [None], the default, every word of the
+    /// wordlist is tried and the resulting tree is optimal, but that is only feasible for small
+    /// wordlists. See [DEFAULT_BREADTH] for larger ones.
+    pub breadth: Option<usize>,
+    /// Only guess words that use all hints revealed so far, see
+    /// [hard mode](crate::game::GameBuilder::hard_mode)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hard_mode: bool,
+}
+
+/// Total amount of guesses for a set of solutions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cost {
+    /// guesses needed to find every solution, added up
+    pub sum: usize,
+    /// guesses needed for the hardest solution
+    pub worst: usize,
+}
+
+impl Cost {
+    fn key(&self, objective: Objective) -> (usize, usize) {
+        match objective {
+            Objective::Expected => (self.sum, self.worst),
+            Objective::WorstCase => (self.worst, self.sum),
+        }
+    }
+}
+
+/// A node of a [DecisionTree]
+///
+/// Tells us what to guess, and where to go next depending on the [Pattern] of the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Node {
+    guess: Word,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    children: BTreeMap<Pattern, Node>,
+}
+
+impl Node {
+    /// the word to guess in this situation
+    pub fn guess(&self) -> &Word {
+        &self.guess
+    }
+
+    /// the node to continue with after getting the [Pattern] as response
+    pub fn child(&self, pattern: Pattern) -> Option<&Node> {
+        self.children.get(&pattern)
+    }
+
+    /// all following nodes, by the [Pattern] that leads to them
+    pub fn children(&self) -> &BTreeMap<Pattern, Node> {
+        &self.children
+    }
+}
+
+/// A precomputed strategy for playing every solution of a [WordList]
+///
+/// The tree contains a [Node] for each situation that can come up in a game. The first guess is
+/// the [root](DecisionTree::root), the response to it decides which child is used for the next
+/// guess, and so on, until the solution was found.
+///
+/// Building a tree is expensive, so it can be [saved](DecisionTree::save) and
+/// [loaded](DecisionTree::load) when the `serde` feature is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DecisionTree {
+    wordlist: String,
+    length: usize,
+    solutions: usize,
+    config: TreeConfig,
+    cost: Cost,
+    root: Node,
+}
+
+impl DecisionTree {
+    /// Build a [DecisionTree] for all [solutions](WordList::solutions) of the wordlist
+    ///
+    /// # Errors
+    ///
+    /// Fails if the wordlist has no solutions or no words with the requested length.
+    pub fn build<WL: WordList>(wl: &WL, length: usize, config: TreeConfig) -> WResult<Self> {
+        Self::build_with_patterns(wl, length, config, None)
+    }
+
+    /// Like [build](Self::build), but look up the patterns in a [PatternMatrix] where possible
+    ///
+    /// # Errors
+    ///
+    /// Fails if the wordlist has no solutions or no words with the requested length.
+    pub fn build_with_patterns<WL: WordList>(
+        wl: &WL,
+        length: usize,
+        config: TreeConfig,
+        patterns: Option<&PatternMatrix>,
+    ) -> WResult<Self> {
+        let solutions: Vec<Word> = wl
+            .solutions()
+            .into_iter()
+            .map(|s| s.0)
+            .filter(|w| w.char_len() == length)
+            .collect();
+        let guesses = guesses_of(wl, length);
+        info!(
+            "building decision tree for {} solutions and {} guesses, this may take a while",
+            solutions.len(),
+            guesses.len()
+        );
+        let (cost, root) = TreeBuilder::new(&solutions, &guesses, &config)
+            .with_patterns(patterns)
+            .build()?;
+        let tree = Self {
+            wordlist: wl.name().to_string(),
+            length,
+            solutions: solutions.len(),
+            config,
+            cost,
+            root,
+        };
+        info!("finished building the decision tree: {tree}");
+        Ok(tree)
+    }
+
+    /// the first guess
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
+
+    /// name of the [WordList] this tree was made for
+    pub fn wordlist(&self) -> &str {
+        &self.wordlist
+    }
+
+    /// length of the words this tree was made for
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// the configuration the tree was built with
+    pub fn config(&self) -> &TreeConfig {
+        &self.config
+    }
+
+    /// total amount of guesses needed for the solutions of the tree
+    pub fn cost(&self) -> Cost {
+        self.cost
+    }
+
+    /// average amount of guesses needed per solution
+    pub fn expected_guesses(&self) -> f64 {
+        self.cost.sum as f64 / self.solutions as f64
+    }
+
+    /// amount of guesses needed for the hardest solution
+    pub fn worst_case(&self) -> usize {
+        self.cost.worst
+    }
+
+    /// Follow the tree along the responses of the game
+    ///
+    /// Returns [None] if the game left the tree, because other words were guessed or because
+    /// the solution is not one the tree was built for.
+    pub fn node_for<WL: WordList>(&self, game: &Game<'_, WL>) -> Option<&Node> {
+        walk(&self.root, game.responses())
+    }
+
+    /// Check if this tree can be used for a wordlist
+    ///
+    /// # Errors
+    ///
+    /// Fails with [SolverError::TreeMismatch] if the tree was built for another wordlist, word
+    /// length or with another configuration.
+    pub fn check<WL: WordList>(&self, wl: &WL, length: usize, config: &TreeConfig) -> WResult<()> {
+        if self.wordlist != wl.name() || self.length != length || self.config != *config {
+            return Err(SolverError::TreeMismatch(format!(
+                "{} with length {} and {:?}",
+                self.wordlist, self.length, self.config
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Save the tree as JSON
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can not be written.
+    #[cfg(feature = "serde")]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> WResult<()> {
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer(std::io::BufWriter::new(file), self)?;
+        Ok(())
+    }
+
+    /// Load a tree that was [saved](DecisionTree::save) before
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can not be read or is not a valid tree.
+    #[cfg(feature = "serde")]
+    pub fn load<P: AsRef<Path>>(path: P) -> WResult<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+
+    /// Default file name for caching a tree built with `config` for a wordlist
+    pub fn cache_file_name<WL: WordList>(wl: &WL, length: usize, config: &TreeConfig) -> String {
+        let name = crate::wlist::sanitized_name(wl.name());
+        let breadth = match config.breadth {
+            Some(b) => b.to_string(),
+            None => "full".to_string(),
+        };
+        let mode = if config.hard_mode { "-hard" } else { "" };
+        format!(
+            "{name}-{length}-{}-{breadth}{mode}.tree.json",
+            config.objective
+        )
+    }
+}
+
+impl Display for DecisionTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} solutions of {} optimized for {} guesses{}: first guess {}, {:.4} guesses on \
+            average, at most {}",
+            self.solutions,
+            self.wordlist,
+            self.config.objective,
+            if self.config.hard_mode {
+                " in hard mode"
+            } else {
+                ""
+            },
+            self.root.guess,
+            self.expected_guesses(),
+            self.worst_case()
+        )
+    }
+}
+
+/// Branch and bound search for the best [Node] for a set of solutions
+struct TreeBuilder<'a> {
+    solutions: &'a [Word],
+    guesses: &'a [Word],
+    config: &'a TreeConfig,
+    /// pattern of a guess that is the solution
+    solved: Pattern,
+    /// optimal results for sets of solutions that were already searched, together with a
+    /// fingerprint of the guesses that were allowed for them
+    memo: HashMap<(Vec<u32>, u64), (Cost, Node)>,
+    lookup: Option<Lookup<'a>>,
+}
+
+/// precomputed patterns with the rows of the guesses and the columns of the solutions
+struct Lookup<'a> {
+    matrix: &'a PatternMatrix,
+    rows: Vec<Option<usize>>,
+    cols: Vec<Option<usize>>,
+}
+
+impl<'a> TreeBuilder<'a> {
+    fn new(solutions: &'a [Word], guesses: &'a [Word], config: &'a TreeConfig) -> Self {
+        let length = solutions.first().map(|w| w.char_len()).unwrap_or(0);
+        Self {
+            solutions,
+            guesses,
+            config,
+            solved: 3_u32.pow(length as u32) - 1,
+            memo: HashMap::new(),
+            lookup: None,
+        }
+    }
+
+    fn with_patterns(mut self, patterns: Option<&'a PatternMatrix>) -> Self {
+        self.lookup = patterns.map(|matrix| Lookup {
+            matrix,
+            rows: self.guesses.iter().map(|g| matrix.guess_index(g)).collect(),
+            cols: self
+                .solutions
+                .iter()
+                .map(|s| matrix.solution_index(s))
+                .collect(),
+        });
+        self
+    }
+
+    /// the pattern of the guess `gidx` if solution `sidx` is the solution
+    fn pattern(&self, gidx: usize, sidx: u32) -> Pattern {
+        if let Some(lookup) = &self.lookup {
+            if let (Some(row), Some(col)) = (lookup.rows[gidx], lookup.cols[sidx as usize]) {
+                return lookup.matrix.pattern_at(row, col);
+            }
+        }
+        pattern(&self.solutions[sidx as usize], &self.guesses[gidx])
+    }
+
+    fn build(&mut self) -> WResult<(Cost, Node)> {
+        if self.solutions.is_empty() || self.guesses.is_empty() {
+            return Err(SolverError::NoMatches(None).into());
+        }
+        let all: Vec<u32> = (0..self.solutions.len() as u32).collect();
+        let allowed: Vec<u32> = (0..self.guesses.len() as u32).collect();
+        let result = self.search(&all, &allowed, usize::MAX);
+        debug!("searched {} distinct sets of solutions", self.memo.len());
+        result.ok_or(SolverError::NoMatches(None).into())
+    }
+
+    /// group the solutions in `set` by the pattern they would give for `guess`
+    fn partition(&self, gidx: usize, set: &[u32]) -> BTreeMap<Pattern, Vec<u32>> {
+        let mut buckets: BTreeMap<Pattern, Vec<u32>> = BTreeMap::new();
+        for idx in set {
+            buckets
+                .entry(self.pattern(gidx, *idx))
+                .or_default()
+                .push(*idx);
+        }
+        buckets
+    }
+
+    /// A lower bound for the cost of playing with these buckets
+    fn lower_bound(&self, set: &[u32], buckets: &BTreeMap<Pattern, Vec<u32>>) -> Cost {
+        let mut cost = Cost {
+            sum: set.len(),
+            worst: 1,
+        };
+        for (p, bucket) in buckets {
+            if *p == self.solved {
+                continue;
+            }
+            // at best, one of them is guessed right with the next guess
+            cost.sum += 2 * bucket.len() - 1;
+            cost.worst = cost.worst.max(if bucket.len() == 1 { 2 } else { 3 });
+        }
+        cost
+    }
+
+    /// Rank the guesses by how well they split the `set`
+    ///
+    /// Guesses that can not give us any information are left out.
+    fn ranked_guesses(&self, set: &[u32], allowed: &[u32]) -> Vec<usize> {
+        let mut scored: Vec<(usize, usize, bool, usize)> = allowed
+            .iter()
+            .map(|gidx| *gidx as usize)
+            .filter_map(|gidx| {
+                let mut sizes: HashMap<Pattern, usize> = HashMap::new();
+                for idx in set {
+                    *sizes.entry(self.pattern(gidx, *idx)).or_default() += 1;
+                }
+                let is_candidate = sizes.contains_key(&self.solved);
+                if sizes.len() == 1 && !is_candidate {
+                    return None;
+                }
+                let biggest = sizes.values().copied().max().unwrap_or(0);
+                Some((gidx, sizes.len(), is_candidate, biggest))
+            })
+            .collect();
+        match self.config.objective {
+            // more buckets usually means less guesses afterwards
+            Objective::Expected => scored.sort_by(|a, b| {
+                b.1.cmp(&a.1)
+                    .then(b.2.cmp(&a.2))
+                    .then(a.3.cmp(&b.3))
+                    .then(a.0.cmp(&b.0))
+            }),
+            // smaller worst buckets usually mean less guesses for the worst case
+            Objective::WorstCase => scored.sort_by(|a, b| {
+                a.3.cmp(&b.3)
+                    .then(b.1.cmp(&a.1))
+                    .then(b.2.cmp(&a.2))
+                    .then(a.0.cmp(&b.0))
+            }),
+        }
+        let mut ranked: Vec<usize> = scored.into_iter().map(|s| s.0).collect();
+        if let Some(breadth) = self.config.breadth {
+            ranked.truncate(breadth);
+        }
+        ranked
+    }
+
+    /// The guesses of `allowed` that use all hints `guess` got with `pattern`
+    fn still_allowed(&self, allowed: &[u32], guess: &Word, pattern: Pattern) -> Vec<u32> {
+        allowed
+            .iter()
+            .copied()
+            .filter(|a| hard_mode_violation(&self.guesses[*a as usize], guess, pattern).is_none())
+            .collect()
+    }
+
+    /// Find the best [Node] for the `set` of solutions
+    ///
+    /// Only the guesses in `allowed` are tried, which are all guesses unless
+    /// [TreeConfig::hard_mode] is set.
+    ///
+    /// `budget` is an exclusive upper bound for the primary cost of the objective, if no node can
+    /// be found that is cheaper, [None] is returned. If a node is returned, it is the best one
+    /// that can be found with the current [TreeConfig].
+    fn search(&mut self, set: &[u32], allowed: &[u32], budget: usize) -> Option<(Cost, Node)> {
+        let objective = self.config.objective;
+        // outside of hard mode, all guesses are always allowed
+        let fingerprint = if self.config.hard_mode {
+            let mut hasher = DefaultHasher::new();
+            allowed.hash(&mut hasher);
+            hasher.finish()
+        } else {
+            0
+        };
+        let key = (set.to_vec(), fingerprint);
+        if let Some((cost, node)) = self.memo.get(&key) {
+            return (cost.key(objective).0 < budget).then(|| (*cost, node.clone()));
+        }
+        if set.len() == 1 {
+            let cost = Cost { sum: 1, worst: 1 };
+            let node = Node {
+                guess: self.solutions[set[0] as usize],
+                children: BTreeMap::new(),
+            };
+            return (cost.key(objective).0 < budget).then_some((cost, node));
+        }
+
+        let mut best: Option<(Cost, Node)> = None;
+        for gidx in self.ranked_guesses(set, allowed) {
+            let guess = &self.guesses[gidx];
+            let buckets = self.partition(gidx, set);
+            // allow ties in the primary cost, they might be better in the secondary one
+            let bound = match &best {
+                Some((cost, _)) => budget.min(cost.key(objective).0 + 1),
+                None => budget,
+            };
+            let lower = self.lower_bound(set, &buckets);
+            if lower.key(objective).0 >= bound
+                || best
+                    .as_ref()
+                    .is_some_and(|(cost, _)| lower.key(objective) >= cost.key(objective))
+            {
+                continue;
+            }
+
+            let mut cost = Cost {
+                sum: set.len(),
+                worst: 1,
+            };
+            let mut remaining_lower = lower.sum - set.len();
+            let mut children = BTreeMap::new();
+            let mut aborted = false;
+            // big buckets first, they are the most likely to exceed the budget
+            let mut buckets: Vec<(Pattern, Vec<u32>)> = buckets
+                .into_iter()
+                .filter(|(p, _)| *p != self.solved)
+                .collect();
+            buckets.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+            for (p, bucket) in buckets {
+                remaining_lower -= 2 * bucket.len() - 1;
+                let child_budget = match objective {
+                    Objective::Expected => bound.saturating_sub(cost.sum + remaining_lower),
+                    Objective::WorstCase => bound.saturating_sub(1),
+                };
+                let result = if self.config.hard_mode {
+                    let child_allowed = self.still_allowed(allowed, guess, p);
+                    self.search(&bucket, &child_allowed, child_budget)
+                } else {
+                    self.search(&bucket, allowed, child_budget)
+                };
+                match result {
+                    Some((child_cost, child)) => {
+                        cost.sum += child_cost.sum;
+                        cost.worst = cost.worst.max(child_cost.worst + 1);
+                        children.insert(p, child);
+                    }
+                    None => {
+                        aborted = true;
+                        break;
+                    }
+                }
+            }
+            if aborted || cost.key(objective).0 >= bound {
+                continue;
+            }
+            if best
+                .as_ref()
+                .is_none_or(|(b, _)| cost.key(objective) < b.key(objective))
+            {
+                trace!(
+                    "new best guess {guess} for {} solutions: {cost:?}",
+                    set.len()
+                );
+                best = Some((
+                    cost,
+                    Node {
+                        guess: *guess,
+                        children,
+                    },
+                ));
+            }
+        }
+
+        if let Some((cost, node)) = &best {
+            self.memo.insert(key, (*cost, node.clone()));
+        }
+        best
+    }
+}
+
+/// follow the `responses` from `node`, [None] if they leave the tree
+fn walk<'n>(mut node: &'n Node, responses: &[GuessResponse]) -> Option<&'n Node> {
+    for response in responses {
+        if *response.guess() != node.guess {
+            return None;
+        }
+        node = node.child(response.evaluation().pattern())?;
+    }
+    Some(node)
+}
+
+/// The [DecisionTree] of a [DecisionTreeSolver] for one word length, built when it is needed
+#[derive(Debug, Default)]
+struct TreeSlot {
+    tree: OnceLock<DecisionTree>,
+    /// held while the tree is built or loaded, so that it only happens once
+    building: Mutex<()>,
+}
+
+/// The [TreeSlot] of a [DecisionTreeSolver] for each word length
+type Trees = [TreeSlot; MAX_WORD_LENGTH + 1];
+
+/// How many trees for games that left the [DecisionTree] a [DecisionTreeSolver] keeps
+///
+/// When there are this many, they are all dropped before the next one is kept.
+pub const MAX_SUBTREES: usize = 1024;
+
+/// Where a tree for a game that left the [DecisionTree] starts: the word length, whether it keeps
+/// to hard mode and the guesses with their patterns that lead to it
+type SubtreeKey = (usize, bool, Vec<(Word, Pattern)>);
+
+/// the guesses of a game with their patterns
+fn history<WL: WordList>(game: &Game<'_, WL>) -> Vec<(Word, Pattern)> {
+    game.responses()
+        .iter()
+        .map(|r| (*r.guess(), r.evaluation().pattern()))
+        .collect()
+}
+
+/// A [Solver] that plays after a precomputed [DecisionTree]
+///
+/// The tree is built when the first guess is needed, which can take a long time for large
+/// wordlists. Use [cache_dir](DecisionTreeSolver::cache_dir) to store it on disk, so that it only
+/// has to be built once for each wordlist and word length.
+///
+/// By default, the tree is optimal: No strategy needs less guesses on average (or in the worst
+/// case, see [Objective]) for the [solutions](WordList::solutions) of the wordlist. This makes it
+/// a good baseline to compare other solvers with. Searching all guesses is only feasible for
+/// small wordlists, for larger ones set a [breadth](DecisionTreeSolver::breadth) like
+/// [DEFAULT_BREADTH] to trade optimality for speed.
+///
+/// If the game leaves the tree, because the solution is not in the solutions of the wordlist or
+/// because other words have been guessed, a new tree is built for the remaining candidates. This
+/// also happens if the game is in [hard mode](Game::hard_mode) and the tree would guess a word
+/// that is not allowed, so use [hard_mode](DecisionTreeSolver::hard_mode) to build a tree that
+/// keeps to the rules of hard mode from the start. Up to [MAX_SUBTREES] of these trees are kept,
+/// so they are only built once for each situation.
+///
+/// One solver can play games of different word lengths, it keeps a tree for each length.
+#[derive(Debug, Clone)]
+pub struct DecisionTreeSolver<'wl, WL> {
+    wl: &'wl WL,
+    config: TreeConfig,
+    cache_dir: Option<PathBuf>,
+    trees: Arc<Trees>,
+    subtrees: Arc<Mutex<HashMap<SubtreeKey, Node>>>,
+}
+
+impl<'wl, WL: WordList> DecisionTreeSolver<'wl, WL> {
+    /// Set what the tree should optimize for
+    pub fn objective(mut self, objective: Objective) -> Self {
+        self.config.objective = objective;
+        self.trees = Arc::default();
+        self.subtrees = Arc::default();
+        self
+    }
+
+    /// Set how many guesses should be tried for each node, see [TreeConfig::breadth]
+    pub fn breadth(mut self, breadth: Option<usize>) -> Self {
+        self.config.breadth = breadth;
+        self.trees = Arc::default();
+        self.subtrees = Arc::default();
+        self
+    }
+
+    /// Build the tree for games in hard mode, see [TreeConfig::hard_mode]
+    pub fn hard_mode(mut self, hard_mode: bool) -> Self {
+        self.config.hard_mode = hard_mode;
+        self.trees = Arc::default();
+        self.subtrees = Arc::default();
+        self
+    }
+
+    /// Load the tree from this directory if it was built before, and save it there otherwise
+    pub fn cache_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.cache_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Use a tree that was built before
+    ///
+    /// # Errors
+    ///
+    /// Fails if the tree does not match the wordlist and configuration of the solver.
+    pub fn with_tree(self, tree: DecisionTree) -> WResult<Self> {
+        tree.check(self.wl, tree.length(), &self.config)?;
+        let trees: Arc<Trees> = Arc::default();
+        trees
+            .get(tree.length())
+            .ok_or(GameError::UnsupportedLength(tree.length()))?
+            .tree
+            .set(tree)
+            .expect("the trees were just created");
+        Ok(Self { trees, ..self })
+    }
+
+    /// Get the tree for games with words of `length`, building or loading it if needed
+    ///
+    /// # Errors
+    ///
+    /// Fails if the tree can not be built.
+    pub fn tree(&self, length: usize) -> WResult<&DecisionTree> {
+        self.tree_with_patterns(length, None)
+    }
+
+    /// Like [tree](Self::tree), but use precomputed patterns if the tree needs to be built
+    ///
+    /// If multiple threads need the tree at once, one builds it and the others wait for it.
+    fn tree_with_patterns(
+        &self,
+        length: usize,
+        patterns: Option<&PatternMatrix>,
+    ) -> WResult<&DecisionTree> {
+        let slot = self
+            .trees
+            .get(length)
+            .ok_or(GameError::UnsupportedLength(length))?;
+        if let Some(tree) = slot.tree.get() {
+            return Ok(tree);
+        }
+        let _building = slot.building.lock().unwrap();
+        // another thread might have built the tree while we waited
+        if let Some(tree) = slot.tree.get() {
+            return Ok(tree);
+        }
+        let tree = self.load_or_build(length, patterns)?;
+        Ok(slot.tree.get_or_init(|| tree))
+    }
+
+    #[cfg(feature = "serde")]
+    fn load_or_build(
+        &self,
+        length: usize,
+        patterns: Option<&PatternMatrix>,
+    ) -> WResult<DecisionTree> {
+        let Some(dir) = &self.cache_dir else {
+            return DecisionTree::build_with_patterns(
+                self.wl,
+                length,
+                self.config.clone(),
+                patterns,
+            );
+        };
+        let path = dir.join(DecisionTree::cache_file_name(self.wl, length, &self.config));
+        if path.exists() {
+            match DecisionTree::load(&path)
+                .and_then(|tree| tree.check(self.wl, length, &self.config).map(|_| tree))
+            {
+                Ok(tree) => {
+                    info!("loaded decision tree from {}", path.display());
+                    return Ok(tree);
+                }
+                Err(e) => warn!("could not use cached tree {}: {e}", path.display()),
+            }
+        }
+        let tree =
+            DecisionTree::build_with_patterns(self.wl, length, self.config.clone(), patterns)?;
+        std::fs::create_dir_all(dir)?;
+        tree.save(&path)?;
+        info!("saved decision tree to {}", path.display());
+        Ok(tree)
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn load_or_build(
+        &self,
+        length: usize,
+        patterns: Option<&PatternMatrix>,
+    ) -> WResult<DecisionTree> {
+        DecisionTree::build_with_patterns(self.wl, length, self.config.clone(), patterns)
+    }
+}
+
+impl<'wl, WL: WordList> Solver<'wl, WL> for DecisionTreeSolver<'wl, WL> {
+    fn build(wordlist: &'wl WL) -> WResult<Self> {
+        info!("using decision tree solver");
+        Ok(Self {
+            wl: wordlist,
+            config: TreeConfig::default(),
+            cache_dir: None,
+            trees: Arc::default(),
+            subtrees: Arc::default(),
+        })
+    }
+    /// The name includes the [TreeConfig], as trees with other configurations play differently
+    fn name(&self) -> String {
+        let breadth = match self.config.breadth {
+            Some(breadth) => breadth.to_string(),
+            None => "all".to_string(),
+        };
+        format!(
+            "DecisionTreeSolver({}, breadth {breadth}{})",
+            self.config.objective,
+            if self.config.hard_mode { ", hard" } else { "" }
+        )
+    }
+    /// Look up the guess in the [DecisionTree]
+    ///
+    /// See [DecisionTreeSolver] for details.
+    fn guess_for(&self, game: &Game<'wl, WL>) -> WResult<Word> {
+        let tree = self.tree_with_patterns(game.length(), game.patterns())?;
+        if let Some(node) = tree.node_for(game) {
+            if game.allows(node.guess()) {
+                return Ok(*node.guess());
+            }
+        }
+        let hard_mode = self.config.hard_mode || game.hard_mode();
+        let mut key: SubtreeKey = (game.length(), hard_mode, history(game));
+        {
+            let subtrees = self.subtrees.lock().unwrap();
+            let responses = game.responses();
+            // the latest subtree the game went through knows the most about it
+            for start in (0..=responses.len()).rev() {
+                key.2.truncate(start);
+                if let Some(node) = subtrees
+                    .get(&key)
+                    .and_then(|root| walk(root, &responses[start..]))
+                {
+                    if game.allows(node.guess()) {
+                        return Ok(*node.guess());
+                    }
+                }
+            }
+        }
+        debug!("the game left the decision tree, building a new one for the candidates");
+        let solutions: Vec<Word> = super::candidates(game).into_iter().map(|c| c.0).collect();
+        let guesses: Vec<Word> = guesses_of(self.wl, game.length())
+            .into_iter()
+            .filter(|g| game.allows(g))
+            .collect();
+        let config = TreeConfig {
+            hard_mode,
+            ..self.config.clone()
+        };
+        let (_cost, node) = TreeBuilder::new(&solutions, &guesses, &config)
+            .with_patterns(game.patterns())
+            .build()
+            .map_err(|_| SolverError::NoMatches(game.solution().cloned()))?;
+        let guess = *node.guess();
+        let mut subtrees = self.subtrees.lock().unwrap();
+        if subtrees.len() >= MAX_SUBTREES {
+            subtrees.clear();
+        }
+        subtrees.insert((game.length(), hard_mode, history(game)), node);
+        Ok(guess)
+    }
+}
+
+impl<'wl, WL: WordList> From<DecisionTreeSolver<'wl, WL>> for AnyBuiltinSolver<'wl, WL> {
+    fn from(value: DecisionTreeSolver<'wl, WL>) -> Self {
+        Self::DecisionTree(value)
+    }
+}
+
+/// all words of the wordlist with `length` in a stable order
+fn guesses_of<WL: WordList>(wl: &WL, length: usize) -> Vec<Word> {
+    let mut guesses: Vec<Word> = wl
+        .wordmap()
+        .keys()
+        .filter(|w| w.char_len() == length)
+        .cloned()
+        .collect();
+    guesses.sort();
+    guesses
+}
@@ -1,11 +1,16 @@
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
+use libpt::log::debug;
 use serde_json;
 
 use crate::error::WordlistError;
 
-use super::{Word, WordList};
+use super::format::{Format, Vocabulary, WordlistFormat};
+use super::word::Alphabet;
+use super::{Probability, Word, WordData, WordList};
 
 pub const RAW_WORDLIST_BUNDLED_ENGLISH: &str =
     include_str!("../../data/wordlists/en_US_3b1b_freq_map.json");
@@ -18,13 +23,18 @@ pub const RAW_WORDLIST_PATH_GERMAN_SMALL: &str = "../../data/wordlists/german_SU
 #[derive(Clone)]
 pub struct BuiltinWList {
     words: super::WordMap,
+    /// the words that can be the solution, if they are known, sorted
+    solutions: Option<Vec<WordData>>,
+    /// the [priors](WordList::priors), computed when they are first needed
+    priors: Arc<OnceLock<HashMap<Word, Probability>>>,
     name: String,
 }
 
 impl BuiltinWList {
     /// load a wordlist from file
     ///
-    /// Wordlist files are expected to have the following format:
+    /// The [Format] is chosen by the extension of the file, or else by it's content. The
+    /// original format is a JSON object of the words and their frequency:
     ///
     /// ```json
     /// {
@@ -32,29 +42,158 @@ impl BuiltinWList {
     /// }
     /// ```
     ///
-    /// Where the number is the frequency. Higher/Lower case is ignored.
+    /// Higher/Lower case is ignored. The solutions are the words that are common enough, see
+    /// [WordList::solutions].
+    ///
+    /// To name the solutions explicitly, the words that can be guessed and the words that can be
+    /// the solution are given separately:
+    ///
+    /// ```json
+    /// {
+    ///     "guesses": {
+    ///         "word": 0.001,
+    ///         "sword": 0.0001
+    ///     },
+    ///     "solutions": ["word"]
+    /// }
+    /// ```
+    ///
+    /// Every solution must be one of the guesses. See [Format] for the other formats.
     ///
     /// Only words with the specified length will be included.
     ///
     /// ## Errors
     ///
-    /// Will fail if the file path cannot be read or the format is wrong.
+    /// Will fail if the file path cannot be read or the format is wrong, or with
+    /// [WordlistError::UnknownSolution] if a solution is not one of the guesses.
     pub fn load<P: AsRef<std::path::Path>>(wl_path: P, len: usize) -> Result<Self, WordlistError> {
+        let (vocabulary, name) = read(wl_path.as_ref(), None)?;
+        Self::from_vocabulary(vocabulary, name, len)
+    }
+
+    /// load a wordlist from a file with the given [WordlistFormat]
+    ///
+    /// Only words with the specified length will be included.
+    ///
+    /// ## Errors
+    ///
+    /// Will fail if the file path cannot be read or does not have the format, or with
+    /// [WordlistError::UnknownSolution] if a solution is not one of the guesses.
+    pub fn load_with<P: AsRef<Path>, F: WordlistFormat>(
+        wl_path: P,
+        len: usize,
+        format: &F,
+    ) -> Result<Self, WordlistError> {
         let path: &Path = wl_path.as_ref();
-        let file = std::fs::File::open(path)?;
+        let content = std::fs::read_to_string(path)?;
+        Self::from_vocabulary(format.parse(&content)?, file_name(path), len)
+    }
 
-        // don't load the whole string into memory
-        let reader = std::io::BufReader::new(file);
-        let mut words: super::WordMap = serde_json::from_reader(reader)?;
-        words.only_words_with_len(len);
+    /// Load the words that can be guessed and the words that can be the solution from two files
+    ///
+    /// Both are wordlist files like for [load](BuiltinWList::load), of the solutions only the
+    /// words are used. A plain list of words works well for the solutions.
+    ///
+    /// Only words with the specified length will be included.
+    ///
+    /// ## Errors
+    ///
+    /// Will fail if a file cannot be read or has the wrong format, or with
+    /// [WordlistError::UnknownSolution] if a solution is not one of the guesses.
+    pub fn load_split<P: AsRef<Path>, Q: AsRef<Path>>(
+        guesses_path: P,
+        solutions_path: Q,
+        len: usize,
+    ) -> Result<Self, WordlistError> {
+        let solutions = Self::load(solutions_path, len)?;
+        let solutions: Vec<Word> = solutions.words.keys().copied().collect();
+        Self::load(guesses_path, len)?.with_solutions(solutions)
+    }
+
+    /// Select a wordlist as it is given on the command line
+    ///
+    /// * `eng` and `ger` are the bundled [english](BuiltinWList::english) and
+    ///   [german](BuiltinWList::german) wordlists
+    /// * `GUESSES,SOLUTIONS` are two files for [load_split](BuiltinWList::load_split)
+    /// * `FORMAT:PATH` is a file of the [Format] named before the colon, like `ranked:words.txt`
+    /// * anything else is the path of a file for [load](BuiltinWList::load)
+    ///
+    /// ## Errors
+    ///
+    /// Will fail if the files cannot be loaded.
+    pub fn select(value: &str, len: usize) -> Result<Self, WordlistError> {
+        Self::select_with(value, len, Alphabet::Native)
+    }
+
+    /// Like [select](BuiltinWList::select), but write the words in the [Alphabet]
+    ///
+    /// The words are written in the alphabet before they are filtered by their length, so with
+    /// [Alphabet::Latin] and a length of 5 the german `bäre` is included as `baere`.
+    ///
+    /// ## Errors
+    ///
+    /// Will fail if the files cannot be loaded.
+    pub fn select_with(value: &str, len: usize, alphabet: Alphabet) -> Result<Self, WordlistError> {
+        let (vocabulary, name) = match value {
+            "eng" => (
+                Format::Json.parse(RAW_WORDLIST_BUNDLED_ENGLISH)?,
+                "(builtin english)".to_string(),
+            ),
+            "ger" => (
+                Format::Json.parse(RAW_WORDLIST_BUNDLED_GERMAN_SMALL)?,
+                "(builtin german)".to_string(),
+            ),
+            _ => read_selected(value)?,
+        };
+        Self::from_vocabulary(vocabulary.written_in(alphabet), name, len)
+    }
 
-        let name: String = if let Some(osstr) = path.file_name() {
-            osstr.to_str().unwrap_or("(no name)").to_string()
-        } else {
-            "(no name)".to_string()
+    fn from_vocabulary(
+        vocabulary: Vocabulary,
+        name: String,
+        len: usize,
+    ) -> Result<Self, WordlistError> {
+        let Vocabulary {
+            mut words,
+            solutions,
+        } = vocabulary;
+        words.only_words_with_len(len);
+        let wl = Self {
+            words,
+            solutions: None,
+            priors: Arc::default(),
+            name,
         };
+        match solutions {
+            Some(solutions) => wl.with_solutions(with_len(solutions, len)),
+            None => Ok(wl),
+        }
+    }
 
-        Ok(Self { words, name })
+    /// Set the words that can be the solution, instead of using the most common words
+    ///
+    /// ## Errors
+    ///
+    /// Fails with [WordlistError::UnknownSolution] if a solution is not one of the words that can
+    /// be guessed.
+    pub fn with_solutions<I: IntoIterator<Item = Word>>(
+        mut self,
+        solutions: I,
+    ) -> Result<Self, WordlistError> {
+        let mut buf: Vec<WordData> = solutions
+            .into_iter()
+            .map(|w| self.get_word(&w).ok_or(WordlistError::UnknownSolution(w)))
+            .collect::<Result<_, _>>()?;
+        buf.sort_by_key(|s| s.0);
+        buf.dedup_by_key(|s| s.0);
+        self.solutions = Some(buf);
+        self.priors = Arc::default();
+        Ok(self)
+    }
+
+    /// are the solutions given explicitly, instead of being the most common words?
+    pub fn has_explicit_solutions(&self) -> bool {
+        self.solutions.is_some()
     }
 
     pub fn english(len: usize) -> Self {
@@ -63,6 +202,8 @@ impl BuiltinWList {
 
         Self {
             words,
+            solutions: None,
+            priors: Arc::default(),
             name: "(builtin english)".to_string(),
         }
     }
@@ -74,21 +215,96 @@ impl BuiltinWList {
 
         Self {
             words,
+            solutions: None,
+            priors: Arc::default(),
             name: "(builtin german)".to_string(),
         }
     }
 }
 
 impl super::WordList for BuiltinWList {
-    fn length_range(&self) -> impl std::ops::RangeBounds<usize> {
-        5..5
+    fn name(&self) -> &str {
+        &self.name
     }
     fn wordmap(&self) -> &super::WordMap {
         &self.words
     }
     fn get_word(&self, word: &Word) -> Option<super::WordData> {
-        self.words.get(word)
+        self.words.inner().get(word).map(|f| (*word, *f))
+    }
+    fn solutions(&self) -> Vec<WordData> {
+        match &self.solutions {
+            Some(solutions) => solutions.clone(),
+            None => self
+                .words
+                .iter()
+                .filter(|(w, _)| self.prior(w) > 0.5)
+                .map(|(w, f)| (*w, *f))
+                .collect(),
+        }
+    }
+    /// The [WordMap::priors], or 1 for each solution and 0 for the other words if the solutions
+    /// are [explicit](BuiltinWList::has_explicit_solutions)
+    fn priors(&self) -> HashMap<Word, Probability> {
+        self.cached_priors().clone()
     }
+    fn prior(&self, word: &Word) -> Probability {
+        self.cached_priors().get(word).copied().unwrap_or(0.0)
+    }
+}
+
+impl BuiltinWList {
+    fn cached_priors(&self) -> &HashMap<Word, Probability> {
+        self.priors.get_or_init(|| match &self.solutions {
+            Some(solutions) => {
+                let mut priors: HashMap<Word, Probability> =
+                    self.words.keys().map(|w| (*w, 0.0)).collect();
+                priors.extend(solutions.iter().map(|s| (s.0, 1.0)));
+                priors
+            }
+            None => self.words.priors(),
+        })
+    }
+}
+
+/// the [Vocabulary] and name of a wordlist file as it is given to [BuiltinWList::select]
+fn read_selected(value: &str) -> Result<(Vocabulary, String), WordlistError> {
+    if Path::new(value).exists() {
+        return read(Path::new(value), None);
+    }
+    if let Some((guesses, solutions)) = value.split_once(',') {
+        let (solutions, _) = read(Path::new(solutions), None)?;
+        let (mut vocabulary, name) = read(Path::new(guesses), None)?;
+        vocabulary.solutions = Some(solutions.words.keys().copied().collect());
+        return Ok((vocabulary, name));
+    }
+    if let Some((format, path)) = value.split_once(':') {
+        if let Ok(format) = format.parse::<Format>() {
+            return read(Path::new(path), Some(format));
+        }
+    }
+    read(Path::new(value), None)
+}
+
+/// read a wordlist file in the [Format], or the format it seems to have
+fn read(path: &Path, format: Option<Format>) -> Result<(Vocabulary, String), WordlistError> {
+    let content = std::fs::read_to_string(path)?;
+    let format = format.unwrap_or_else(|| Format::of(path, &content));
+    debug!("loading {} as {format:?}", path.display());
+    Ok((format.parse(&content)?, file_name(path)))
+}
+
+/// the name of a wordlist loaded from `path`
+fn file_name(path: &Path) -> String {
+    match path.file_name() {
+        Some(osstr) => osstr.to_str().unwrap_or("(no name)").to_string(),
+        None => "(no name)".to_string(),
+    }
+}
+
+/// only the words with `len` letters
+fn with_len(words: Vec<Word>, len: usize) -> Vec<Word> {
+    words.into_iter().filter(|w| w.char_len() == len).collect()
 }
 
 impl Default for BuiltinWList {
@@ -102,17 +318,28 @@ impl Debug for BuiltinWList {
         f.debug_struct("BuiltinWList")
             .field("name", &self.name)
             .field("words", &self.words)
+            .field("solutions", &self.solutions.as_ref().map(|s| s.len()))
             .finish()
     }
 }
 
 impl Display for BuiltinWList {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let range = self.words.freq_range();
         writeln!(
             f,
-            "{}:\nwords:\t{}\ntop 5:\t{:?}",
+            "{}:\nwords:\t{}\nsolutions:\t{}{}\ncommon:\t{}\nfrequencies:\t{:e} - {:e}\ntop 5:\t{:?}",
             self.name,
+            self.wordmap().len(),
             self.len(),
+            if self.has_explicit_solutions() {
+                " (explicit)"
+            } else {
+                " (prior > 0.5)"
+            },
+            self.words.n_common(),
+            range.start(),
+            range.end(),
             self.n_most_likely(5)
         )
     }
@@ -0,0 +1,236 @@
+//! The file formats a [BuiltinWList](super::builtin::BuiltinWList) can be loaded from
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::error::WordlistError;
+
+use super::word::{Alphabet, Frequency, Word, WordMap};
+
+/// The words read from a wordlist file
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    /// the words that can be guessed, with their frequency
+    pub words: WordMap,
+    /// the words that can be the solution, if the file names them
+    pub solutions: Option<Vec<Word>>,
+}
+
+impl Vocabulary {
+    /// Write the words and solutions in the [Alphabet], see [WordMap::write_in]
+    pub fn written_in(mut self, alphabet: Alphabet) -> Self {
+        self.words.write_in(alphabet);
+        self.solutions = self.solutions.map(|solutions| {
+            solutions
+                .iter()
+                .filter_map(|w| alphabet.word(w).ok())
+                .collect()
+        });
+        self
+    }
+}
+
+/// A way to read a [Vocabulary] from the content of a file
+///
+/// The builtin formats are the variants of [Format]. Implement this to load wordlists of other
+/// formats with [BuiltinWList::load_with](super::builtin::BuiltinWList::load_with).
+pub trait WordlistFormat {
+    /// Read the words from the content of a file
+    ///
+    /// # Errors
+    ///
+    /// Fails if the content does not have the format.
+    fn parse(&self, content: &str) -> Result<Vocabulary, WordlistError>;
+}
+
+/// The builtin wordlist formats
+///
+/// | format | extension | content |
+/// |--------|-----------|---------|
+/// | [Json](Format::Json) | `.json` | `{"word": 0.001}`, or the guesses and solutions separately |
+/// | [Plain](Format::Plain) | `.txt` | words separated by whitespace, all equally frequent |
+/// | [Ranked](Format::Ranked) | | like plain, but the most frequent word first |
+/// | [Tsv](Format::Tsv) | `.tsv` | a word and it's count or frequency per line, tab separated |
+/// | [Csv](Format::Csv) | `.csv` | like tsv, but comma separated |
+///
+/// Lines of plain and ranked lists that start with `#` are comments. Tables may have a header
+/// and more columns, which are ignored. The numbers are taken as frequencies, unless one is more
+/// than 1, then they are counts and divided by their sum.
+///
+/// For the JSON format with separate solutions, see
+/// [BuiltinWList::load](super::builtin::BuiltinWList::load).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Json,
+    Plain,
+    Ranked,
+    Tsv,
+    Csv,
+}
+
+impl Format {
+    /// Choose the format by the extension of the file name, [None] if it is not known
+    ///
+    /// Plain and ranked lists both use `.txt`, which is read as [Plain](Format::Plain).
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "txt" | "lst" => Some(Self::Plain),
+            "tsv" | "tab" => Some(Self::Tsv),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// Guess the format by looking at the content
+    ///
+    /// Ranked lists can not be told apart from [Plain](Format::Plain) ones and are read as such.
+    pub fn detect(content: &str) -> Self {
+        let trimmed = content.trim_start();
+        if trimmed.starts_with('{') {
+            return Self::Json;
+        }
+        let first = trimmed
+            .lines()
+            .find(|l| !l.trim().is_empty() && !l.starts_with('#'))
+            .unwrap_or("");
+        if first.contains('\t') {
+            Self::Tsv
+        } else if first.contains(',') {
+            Self::Csv
+        } else {
+            Self::Plain
+        }
+    }
+
+    /// Choose the format for a file: by it's extension, or else by it's content
+    pub fn of(path: &Path, content: &str) -> Self {
+        Self::from_extension(path).unwrap_or_else(|| Self::detect(content))
+    }
+}
+
+impl FromStr for Format {
+    type Err = WordlistError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "json" => Self::Json,
+            "plain" | "txt" => Self::Plain,
+            "ranked" => Self::Ranked,
+            "tsv" => Self::Tsv,
+            "csv" => Self::Csv,
+            _ => return Err(WordlistError::UnknownFormat(s.to_string())),
+        })
+    }
+}
+
+impl WordlistFormat for Format {
+    fn parse(&self, content: &str) -> Result<Vocabulary, WordlistError> {
+        match self {
+            Self::Json => parse_json(content),
+            Self::Plain => {
+                let words = list_words(content)?;
+                let freq = 1.0 / words.len().max(1) as Frequency;
+                Ok(vocabulary(words.into_iter().map(|w| (w, freq)).collect()))
+            }
+            Self::Ranked => {
+                // Zipf's law: the frequency of a word is inversely proportional to it's rank
+                let words = list_words(content)?;
+                let mut map: HashMap<Word, Frequency> = HashMap::new();
+                for (rank, w) in words.into_iter().enumerate() {
+                    map.entry(w).or_insert(1.0 / (rank + 1) as Frequency);
+                }
+                Ok(vocabulary(normalized(map)))
+            }
+            Self::Tsv => parse_table(content, b'\t'),
+            Self::Csv => parse_table(content, b','),
+        }
+    }
+}
+
+/// The formats a JSON wordlist can have
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonFile {
+    Split {
+        guesses: WordMap,
+        solutions: Vec<Word>,
+    },
+    Flat(WordMap),
+}
+
+fn parse_json(content: &str) -> Result<Vocabulary, WordlistError> {
+    Ok(match serde_json::from_str(content)? {
+        JsonFile::Split { guesses, solutions } => Vocabulary {
+            words: guesses,
+            solutions: Some(solutions),
+        },
+        JsonFile::Flat(words) => Vocabulary {
+            words,
+            solutions: None,
+        },
+    })
+}
+
+/// the words of a plain list, without comments, in their order
+fn list_words(content: &str) -> Result<Vec<Word>, WordlistError> {
+    content
+        .lines()
+        .filter(|l| !l.trim_start().starts_with('#'))
+        .flat_map(str::split_whitespace)
+        .map(|w| w.to_lowercase().parse())
+        .collect()
+}
+
+fn parse_table(content: &str, delimiter: u8) -> Result<Vocabulary, WordlistError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .comment(Some(b'#'))
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let mut map: HashMap<Word, Frequency> = HashMap::new();
+    for (idx, record) in reader.records().enumerate() {
+        let record = record?;
+        let line = record.position().map_or(idx + 1, |p| p.line() as usize);
+        let (Some(word), Some(value)) = (record.get(0), record.get(1)) else {
+            return Err(WordlistError::BadLine(
+                line,
+                "expected a word and a number".to_string(),
+            ));
+        };
+        let value: Frequency = match value.parse() {
+            Ok(v) => v,
+            // the header
+            Err(_) if idx == 0 => continue,
+            Err(_) => {
+                return Err(WordlistError::BadLine(
+                    line,
+                    format!("not a number: {value}"),
+                ))
+            }
+        };
+        *map.entry(word.to_lowercase().parse()?).or_default() += value;
+    }
+    if map.values().any(|v| *v > 1.0) {
+        map = normalized(map);
+    }
+    Ok(vocabulary(map))
+}
+
+/// divide the values by their sum
+fn normalized(map: HashMap<Word, Frequency>) -> HashMap<Word, Frequency> {
+    let total: Frequency = map.values().sum();
+    map.into_iter().map(|(w, f)| (w, f / total)).collect()
+}
+
+fn vocabulary(words: HashMap<Word, Frequency>) -> Vocabulary {
+    Vocabulary {
+        words: words.into(),
+        solutions: None,
+    }
+}
@@ -2,39 +2,93 @@ use rand::seq::IteratorRandom;
 
 use regex::Regex;
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Display;
-use std::ops::RangeBounds;
+use std::ops::RangeInclusive;
 
 #[cfg(feature = "builtin")]
 pub mod builtin;
+#[cfg(feature = "builtin")]
+pub mod format;
 pub mod word;
 use word::*;
 
 use crate::error::{WResult, WordlistError};
+#[cfg(feature = "game")]
+use crate::game::constraints::Constraints;
 
 pub type AnyWordlist = Box<dyn WordList>;
 
+/// A vocabulary of words that can be guessed, and the words among them that can be the solution
+///
+/// The [wordmap](WordList::wordmap) holds every word that is accepted as a guess, with it's
+/// frequency. The [solutions](WordList::solutions) are the words a game can be played with. Like
+/// in the real Wordle, there are usually far more words that can be guessed than solutions.
 pub trait WordList: Clone + std::fmt::Debug + Default + Sync + Display {
+    /// The words that can be the solution of a game
+    ///
+    /// By default, these are the words with a [prior](WordList::prior) of more than 0.5.
+    /// Wordlists that know their solutions should return them instead.
     fn solutions(&self) -> Vec<WordData> {
-        let wmap = self.wordmap().clone();
-        let threshold = wmap.threshold();
-        wmap.iter()
-            .filter(|i| *i.1 > threshold)
-            .map(|p| (p.0.clone(), *p.1))
+        let priors = self.priors();
+        self.wordmap()
+            .iter()
+            .filter(|(w, _)| priors.get(*w).is_some_and(|p| *p > 0.5))
+            .map(|(w, f)| (*w, *f))
             .collect()
     }
-    fn rand_solution(&self) -> WordData {
+    /// The probability of each word to be a plausible solution, see [WordMap::priors]
+    ///
+    /// Solvers weight the candidates with these, and the [solutions](WordList::solutions) are
+    /// the words that are more likely than not.
+    fn priors(&self) -> HashMap<Word, Probability> {
+        self.wordmap().priors()
+    }
+    /// The probability of `word` to be a plausible solution, 0 if it's not in the wordlist
+    ///
+    /// Wordlists should cache the [priors](WordList::priors), this is called for every candidate
+    /// of every guess.
+    fn prior(&self, word: &Word) -> Probability {
+        self.priors().get(word).copied().unwrap_or(0.0)
+    }
+    /// Get a random word with `length` [letters](Word::char_len) of the
+    /// [solutions](WordList::solutions), each one is equally likely
+    ///
+    /// [None] if no solution has that length.
+    fn rand_solution(&self, length: usize) -> Option<WordData> {
         let mut rng = rand::thread_rng();
-        let sol = self.solutions().iter().choose(&mut rng).unwrap().clone();
-        (sol.0.to_owned(), sol.1.to_owned())
+        self.solutions()
+            .into_iter()
+            .filter(|s| s.0.char_len() == length)
+            .choose(&mut rng)
     }
     fn rand_word(&self) -> WordData {
         let mut rng = rand::thread_rng();
         let w = self.wordmap().iter().choose(&mut rng).unwrap();
-        (w.0.clone(), *w.1)
+        (*w.0, *w.1)
+    }
+    /// The lengths of the words of the wordlist, in [letters](Word::char_len)
+    fn lengths(&self) -> BTreeSet<usize> {
+        self.wordmap().keys().map(Word::char_len).collect()
+    }
+    /// does the wordlist have words with `length` [letters](Word::char_len)?
+    fn has_length(&self, length: usize) -> bool {
+        self.wordmap().keys().any(|w| w.char_len() == length)
     }
-    fn length_range(&self) -> impl RangeBounds<usize>;
+    /// The shortest and the longest word of the wordlist, in [letters](Word::char_len)
+    ///
+    /// The range is empty if the wordlist has no words. Not every length in the range needs to
+    /// have words, see [lengths](WordList::lengths).
+    fn length_range(&self) -> RangeInclusive<usize> {
+        let lengths = self.lengths();
+        match (lengths.first(), lengths.last()) {
+            (Some(min), Some(max)) => *min..=*max,
+            #[allow(clippy::reversed_empty_ranges)]
+            _ => 1..=0,
+        }
+    }
+    /// A human readable name to identify the wordlist
+    fn name(&self) -> &str;
     #[must_use]
     fn len(&self) -> usize {
         self.solutions().len()
@@ -59,14 +113,9 @@ pub trait WordList: Clone + std::fmt::Debug + Default + Sync + Display {
     fn n_most_likely(&self, n: usize) -> Vec<WordData> {
         self.sort_likelihood().into_iter().take(n).collect()
     }
+    /// the [solutions](WordList::solutions) as [WordMap]
     fn over_threashold(&self) -> WordMap {
-        let wmap = self.wordmap();
-        let threshold = wmap.threshold();
-        let wpairs: Vec<(_, _)> = wmap.iter().filter(|i| *i.1 > threshold).collect();
-        let mut hm = HashMap::new();
-        for (k, v) in wpairs {
-            hm.insert(k.into(), *v);
-        }
+        let hm: HashMap<Word, Frequency> = self.solutions().into_iter().collect();
         WordMap::from(hm)
     }
     fn get_word(&self, word: &Word) -> Option<WordData>;
@@ -93,18 +142,52 @@ pub trait WordList: Clone + std::fmt::Debug + Default + Sync + Display {
         }
         buf
     }
+    /// Get all words that match the regex `pattern` as a whole, the most likely first
     fn get_words_matching(&self, pattern: &str) -> WResult<Vec<WordData>> {
-        let pattern = Regex::new(pattern).map_err(WordlistError::from)?;
-        let hay = self.raw_wordlist();
-        let keys = pattern.captures_iter(&hay);
-        let mut buf = Vec::new();
-        for k in keys {
-            let w: WordData = self.wordmap().get(&k[0]).unwrap();
-            buf.push(w)
-        }
-        // sort by frequency
-        buf.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-        buf.reverse();
+        let pattern = Regex::new(&format!("^(?:{pattern})$")).map_err(WordlistError::from)?;
+        let mut buf: Vec<WordData> = self
+            .wordmap()
+            .iter()
+            .filter(|(w, _)| pattern.is_match(w))
+            .map(|(w, f)| (*w, *f))
+            .collect();
+        sort_by_frequency(&mut buf);
         Ok(buf)
     }
+    /// Get all words that fit the [Constraints], the most likely first
+    ///
+    /// This considers every word of the wordlist, not only the [solutions](WordList::solutions).
+    #[cfg(feature = "game")]
+    fn words_matching(&self, constraints: &Constraints) -> Vec<WordData> {
+        let mut buf: Vec<WordData> = self
+            .wordmap()
+            .iter()
+            .filter(|(w, _)| constraints.matches(w))
+            .map(|(w, f)| (*w, *f))
+            .collect();
+        sort_by_frequency(&mut buf);
+        buf
+    }
+    /// Get all [solutions](WordList::solutions) that fit the [Constraints], the most likely first
+    #[cfg(feature = "game")]
+    fn solutions_matching(&self, constraints: &Constraints) -> Vec<WordData> {
+        let mut buf = constraints.filter(self.solutions());
+        sort_by_frequency(&mut buf);
+        buf
+    }
+}
+
+/// Sort words by their frequency, the most likely first
+///
+/// Words with the same frequency are sorted alphabetically, so the order does not depend on the
+/// order of the [WordMap].
+fn sort_by_frequency(words: &mut [WordData]) {
+    words.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then(a.0.cmp(&b.0)));
+}
+
+/// Make the name of a wordlist usable in file names
+pub(crate) fn sanitized_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
 }
@@ -1,20 +1,238 @@
 use std::collections::HashMap;
-use std::fmt::write;
+use std::fmt::{write, Display};
+use std::ops::Deref;
+use std::str::FromStr;
 
-use libpt::log::trace;
+use libpt::log::{trace, warn};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::error::WordlistError;
 
 pub type Frequency = f64;
+/// The probability of a word to be the solution, from 0 to 1
+pub type Probability = f64;
+
+/// The share of the total frequency that the [common](WordMap::n_common) words make up
+pub const COMMON_MASS: Frequency = 0.99;
+/// How wide the sigmoid of the [priors](WordMap::priors) is on the x axis
+pub const SIGMOID_WIDTH: f64 = 10.0;
+
+/// How many bytes of UTF-8 a [Word] can hold at most
+pub const MAX_WORD_BYTES: usize = 31;
+
+/// A word that can be guessed, stored inline without any allocation
+///
+/// The word is kept as UTF-8, so it can be used as a [str] through [Deref]. Unlike a [String] it
+/// is [Copy] and cheap to hash and compare, which matters a lot for solvers that look at every
+/// word of a wordlist for every guess.
+///
+/// Create a [Word] from a string with [FromStr] or [TryFrom], and turn it back into one with
+/// [Display] or [as_str](Word::as_str). The string is [normalized](normalize) first, so `Größe`
+/// and `größe` are the same word.
+///
+/// Note that [str::len] counts bytes, use [char_len](Word::char_len) for the number of letters.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Word {
+    // the bytes come first, so that the derived ordering is the ordering of the strings
+    bytes: [u8; MAX_WORD_BYTES],
+    len: u8,
+}
+
+impl Word {
+    /// an empty word
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; MAX_WORD_BYTES],
+            len: 0,
+        }
+    }
+
+    /// get the word as [str]
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len as usize])
+            .expect("the bytes are always copied from a valid str")
+    }
+
+    /// how many letters the word has
+    ///
+    /// This is the length that counts in a game, `größe` has 5 letters but 7 bytes.
+    pub fn char_len(&self) -> usize {
+        if self.is_ascii() {
+            self.len as usize
+        } else {
+            self.chars().count()
+        }
+    }
+}
+
+/// Bring a word into the form the words of a wordlist have
+///
+/// The word is written in lower case and in the Unicode normal form C, so that a letter like `ä`
+/// is the same char, no matter if it was written as one code point or as `a` with a combining
+/// diaeresis. A capital `ẞ` becomes `ß`.
+///
+/// ```
+/// use wordle_analyzer::wlist::word::normalize;
+///
+/// assert_eq!(normalize("GRÖẞE"), "größe");
+/// assert_eq!(normalize("Ba\u{308}ren"), "bären");
+/// ```
+pub fn normalize(word: &str) -> String {
+    word.chars().flat_map(char::to_lowercase).nfc().collect()
+}
+
+/// Write a [normalized](normalize) word with the letters `a` to `z` only
+///
+/// The german umlauts and `ß` are written out as `ae`, `oe`, `ue` and `ss`, like the ligatures
+/// `æ` and `œ`. Other letters lose their diacritics, `é` becomes `e`. Note that the word can get
+/// longer.
+///
+/// ```
+/// use wordle_analyzer::wlist::word::transliterate;
+///
+/// assert_eq!(transliterate("größe"), "groesse");
+/// assert_eq!(transliterate("café"), "cafe");
+/// ```
+pub fn transliterate(word: &str) -> String {
+    let mut buf = String::with_capacity(word.len());
+    for c in normalize(word).chars() {
+        match c {
+            'ä' | 'æ' => buf.push_str("ae"),
+            'ö' | 'œ' | 'ø' => buf.push_str("oe"),
+            'ü' => buf.push_str("ue"),
+            'ß' => buf.push_str("ss"),
+            'å' => buf.push_str("aa"),
+            c => buf.extend(std::iter::once(c).nfd().filter(|c| !is_combining_mark(*c))),
+        }
+    }
+    buf
+}
+
+/// The letters the words of a wordlist are written with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alphabet {
+    /// the letters of the language, only [normalized](normalize)
+    #[default]
+    Native,
+    /// the letters `a` to `z`, other letters are [transliterated](transliterate)
+    Latin,
+}
+
+impl Alphabet {
+    /// Read a [Word] written in this alphabet
+    ///
+    /// # Errors
+    ///
+    /// Fails if the word does not fit into a [Word].
+    pub fn word(&self, word: &str) -> Result<Word, WordlistError> {
+        match self {
+            Self::Native => word.parse(),
+            Self::Latin => transliterate(word).parse(),
+        }
+    }
+}
+
+impl Default for Word {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Word {
+    type Target = str;
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Word {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl TryFrom<&str> for Word {
+    type Error = WordlistError;
+    /// # Errors
+    ///
+    /// Fails with [WordlistError::WordTooLong] if the [normalized](normalize) word has more than
+    /// [MAX_WORD_BYTES] bytes.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if !value.is_ascii() || value.bytes().any(|b| b.is_ascii_uppercase()) {
+            return Self::from_normalized(&normalize(value));
+        }
+        Self::from_normalized(value)
+    }
+}
+
+impl Word {
+    fn from_normalized(value: &str) -> Result<Self, WordlistError> {
+        if value.len() > MAX_WORD_BYTES {
+            return Err(WordlistError::WordTooLong(value.to_string()));
+        }
+        let mut word = Self::new();
+        word.bytes[..value.len()].copy_from_slice(value.as_bytes());
+        word.len = value.len() as u8;
+        Ok(word)
+    }
+}
+
+impl FromStr for Word {
+    type Err = WordlistError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl PartialEq<str> for Word {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Word {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Display for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::fmt::Debug for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Word {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Word {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        Self::try_from(s.as_ref()).map_err(serde::de::Error::custom)
+    }
+}
 
-// PERF: Hash for String is probably a bottleneck
-pub type Word = String;
 pub type WordData = (Word, Frequency);
 pub type WordDataRef<'wl> = (&'wl Word, &'wl Frequency);
 pub(crate) type WordMapInner = HashMap<Word, Frequency>;
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "HashMap<String, Frequency>"))]
 pub struct WordMap {
     #[serde(flatten)]
     inner: WordMapInner,
@@ -32,21 +250,23 @@ impl WordMap {
             inner: HashMap::new(),
         }
     }
-    pub fn keys(&self) -> std::collections::hash_map::Keys<'_, String, Frequency> {
+    pub fn keys(&self) -> std::collections::hash_map::Keys<'_, Word, Frequency> {
         self.inner.keys()
     }
-    pub fn values(&self) -> std::collections::hash_map::Values<'_, String, Frequency> {
+    pub fn values(&self) -> std::collections::hash_map::Values<'_, Word, Frequency> {
         self.inner.values()
     }
-    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, String, Frequency> {
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, Word, Frequency> {
         self.inner.iter()
     }
-    pub fn freq_range(&self) -> std::ops::Range<Frequency> {
-        // TODO: calculate this instead of estimating like this
-        return 0.1e-10..1e-6;
-        let lowest: Frequency = todo!();
-        let highest: Frequency = todo!();
-        lowest..highest
+    /// the lowest and the highest frequency of the words, `0.0..=0.0` if there are no words
+    pub fn freq_range(&self) -> std::ops::RangeInclusive<Frequency> {
+        let mut values = self.inner.values().copied();
+        let Some(first) = values.next() else {
+            return 0.0..=0.0;
+        };
+        let (min, max) = values.fold((first, first), |(min, max), f| (min.min(f), max.max(f)));
+        min..=max
     }
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -55,19 +275,83 @@ impl WordMap {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+    /// How many words are relatively common
+    ///
+    /// These are the most frequent words that together make up [COMMON_MASS] of the total
+    /// frequency. If no word has a frequency, all words are common.
     pub fn n_common(&self) -> usize {
-        // TODO: calculate the amount of relatively common words
-        3000
+        let ranked = self.ranked();
+        let total: Frequency = ranked.iter().map(|w| w.1).sum();
+        if total <= 0.0 {
+            return ranked.len();
+        }
+        let mut mass = 0.0;
+        for (idx, (_, f)) in ranked.iter().rev().enumerate() {
+            mass += f;
+            if mass >= COMMON_MASS * total {
+                return idx + 1;
+            }
+        }
+        ranked.len()
     }
+    /// the frequency of the least frequent of the [common](WordMap::n_common) words
     pub fn threshold(&self) -> Frequency {
-        // HACK: I completely butchered the math here
-        // see https://github.com/3b1b/videos/blob/master/_2022/wordle/simulations.py
-        let l_under_sigmoid = 10_f64;
-        let len = self.len();
-        let mut c: f64 = l_under_sigmoid * (0.5 + self.n_common() as f64 / len as f64);
-        c *= 1e-7;
-        trace!(threshold = c);
-        c
+        let n_common = self.n_common();
+        let ranked = self.ranked();
+        let threshold = ranked
+            .get(ranked.len() - n_common)
+            .map(|w| w.1)
+            .unwrap_or(0.0);
+        trace!(threshold);
+        threshold
+    }
+    /// The probability of each word to be a plausible solution
+    ///
+    /// The words are ranked by their frequency and placed evenly on the x axis of a sigmoid
+    /// function, [SIGMOID_WIDTH] wide, the least frequent word left. The sigmoid is centered so
+    /// that the [common](WordMap::n_common) words get a prior of more than 0.5. Words with the
+    /// same frequency share the average of their positions.
+    ///
+    /// This is the model of the wordle simulation by 3Blue1Brown, see
+    /// <https://github.com/3b1b/videos/blob/master/_2022/wordle/simulations.py>.
+    pub fn priors(&self) -> HashMap<Word, Probability> {
+        let ranked = self.ranked();
+        let n = ranked.len();
+        if n == 0 {
+            return HashMap::new();
+        }
+        let center = SIGMOID_WIDTH * (-0.5 + self.n_common() as f64 / n as f64);
+        let step = if n > 1 {
+            SIGMOID_WIDTH / (n - 1) as f64
+        } else {
+            0.0
+        };
+        let left = if n > 1 {
+            center - SIGMOID_WIDTH / 2.0
+        } else {
+            center
+        };
+        let mut priors = HashMap::with_capacity(n);
+        let mut start = 0;
+        while start < n {
+            let mut end = start + 1;
+            while end < n && ranked[end].1 == ranked[start].1 {
+                end += 1;
+            }
+            let position = (start + end - 1) as f64 / 2.0;
+            let prior = sigmoid(left + step * position);
+            for (w, _) in &ranked[start..end] {
+                priors.insert(*w, prior);
+            }
+            start = end;
+        }
+        priors
+    }
+    /// the words, the least frequent first
+    fn ranked(&self) -> Vec<WordData> {
+        let mut ranked: Vec<WordData> = self.iter().map(|(w, f)| (*w, *f)).collect();
+        ranked.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0)));
+        ranked
     }
     pub fn inner(&self) -> &HashMap<Word, Frequency> {
         &self.inner
@@ -76,9 +360,8 @@ impl WordMap {
         &mut self.inner
     }
     pub fn get<I: std::fmt::Display>(&self, word: I) -> Option<WordData> {
-        self.inner
-            .get(&word.to_string())
-            .map(|f| (word.to_string(), *f))
+        let word: Word = word.to_string().parse().ok()?;
+        self.inner.get(&word).map(|f| (word, *f))
     }
     pub fn from_absolute(abs: HashMap<Word, usize>) -> Self {
         let n: f64 = abs.keys().len() as f64;
@@ -86,16 +369,37 @@ impl WordMap {
             abs.into_iter().map(|p| (p.0, p.1 as f64 / n)).collect();
         relative.into()
     }
+    /// Write the words in the [Alphabet]
+    ///
+    /// Words that are written the same afterwards are merged, their frequencies are added up.
+    pub fn write_in(&mut self, alphabet: Alphabet) {
+        if alphabet == Alphabet::Native {
+            return;
+        }
+        let mut inner = WordMapInner::with_capacity(self.inner.len());
+        for (w, f) in self.inner.drain() {
+            match alphabet.word(&w) {
+                Ok(w) => *inner.entry(w).or_default() += f,
+                Err(e) => warn!("left out a word: {e}"),
+            }
+        }
+        self.inner = inner;
+    }
+    /// only keep the words with `len` [letters](Word::char_len)
     pub fn only_words_with_len(&mut self, len: usize) {
         self.inner = self
             .inner
             .iter()
-            .filter(|a| a.0.len() == len)
-            .map(|a| (a.0.to_owned(), *a.1))
+            .filter(|a| a.0.char_len() == len)
+            .map(|a| (*a.0, *a.1))
             .collect::<WordMapInner>();
     }
 }
 
+fn sigmoid(x: f64) -> Probability {
+    1.0 / (1.0 + (-x).exp())
+}
+
 impl std::fmt::Debug for WordMap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write(
@@ -120,6 +424,25 @@ impl From<HashMap<Word, Frequency>> for WordMap {
     }
 }
 
+impl From<HashMap<String, Frequency>> for WordMap {
+    /// Words that are too long for a [Word] are left out. Words that are the same once they are
+    /// [normalized](normalize), like `Haus` and `haus`, are merged and their frequencies added up.
+    fn from(value: HashMap<String, Frequency>) -> Self {
+        let mut inner = WordMapInner::with_capacity(value.len());
+        let mut too_long = 0;
+        for (w, f) in value {
+            match w.parse::<Word>() {
+                Ok(w) => *inner.entry(w).or_default() += f,
+                Err(_) => too_long += 1,
+            }
+        }
+        if too_long > 0 {
+            warn!("left out {too_long} words that are longer than {MAX_WORD_BYTES} bytes");
+        }
+        Self { inner }
+    }
+}
+
 impl From<WordMap> for HashMap<Word, Frequency> {
     fn from(value: WordMap) -> Self {
         value.inner
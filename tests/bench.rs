@@ -0,0 +1,392 @@
+use test_log::test; // set the log level with an envvar: `RUST_LOG=trace cargo test`
+
+use wordle_analyzer::bench::baseline::{Baseline, Tolerance};
+use wordle_analyzer::bench::builtin::BuiltinBenchmark;
+use wordle_analyzer::bench::compare::Comparison;
+use wordle_analyzer::bench::report::Report;
+use wordle_analyzer::bench::{Benchmark, Targets};
+use wordle_analyzer::error::{BenchError, Error};
+use wordle_analyzer::game::Game;
+use wordle_analyzer::solve::{
+    AnyBuiltinSolver, DecisionTreeSolver, EntropySolver, NaiveSolver, Solver,
+};
+use wordle_analyzer::wlist::builtin::BuiltinWList;
+use wordle_analyzer::wlist::WordList;
+
+fn small_wordlist() -> BuiltinWList {
+    BuiltinWList::load("data/wordlists/test_small.json", 5).unwrap()
+}
+
+#[test]
+fn test_report_statistics() {
+    let wl = BuiltinWList::default();
+    let mut report = Report::new(Game::builder(&wl).build().unwrap());
+    // won with 1, 2, 2 and 3 guesses
+    for guesses in [
+        vec!["crane"],
+        vec!["slate", "crane"],
+        vec!["brace", "crane"],
+    ] {
+        let mut game = Game::builder(&wl)
+            .solution(wl.get_word(&"crane".parse().unwrap()))
+            .build()
+            .unwrap();
+        let mut response = None;
+        for guess in guesses {
+            response = Some(game.guess(&guess.parse().unwrap(), None).unwrap());
+        }
+        report.add(response.unwrap());
+    }
+    let mut game = Game::builder(&wl)
+        .solution(wl.get_word(&"slate".parse().unwrap()))
+        .build()
+        .unwrap();
+    for guess in ["crane", "brace", "slate"] {
+        let response = game.guess(&guess.parse().unwrap(), None).unwrap();
+        if response.finished() {
+            report.add(response);
+        }
+    }
+
+    assert_eq!(report.n(), 4);
+    assert_eq!(report.total_steps(), 8);
+    assert_eq!(report.avg_steps(), 2.0);
+    assert_eq!(report.distribution(), vec![1, 2, 1, 0, 0, 0]);
+    assert_eq!(report.median_steps(), 2.0);
+    assert_eq!(report.percentile_steps(25.0), 1);
+    assert_eq!(report.percentile_steps(100.0), 3);
+    assert!((report.std_dev_steps() - (2.0_f64 / 3.0).sqrt()).abs() < 1e-9);
+    assert_eq!(report.avg_score(), 2.0);
+    let (low, high) = report.avg_score_interval();
+    assert!(low < 2.0 && 2.0 < high);
+    let worst = report.worst(1);
+    assert_eq!(worst[0].steps(), 3);
+    assert_eq!(worst[0].solution().unwrap().as_str(), "slate");
+}
+
+#[test]
+fn test_report_statistics_with_losses() {
+    let wl = BuiltinWList::default();
+    let builder = Game::builder(&wl)
+        .max_steps(2)
+        .solution(wl.get_word(&"crane".parse().unwrap()));
+    let mut report = Report::new(builder.build().unwrap());
+    // won with 1 guess in 10ms, lost after 2 guesses in 30ms
+    for (guesses, ms) in [(vec!["crane"], 10), (vec!["slate", "brace"], 30)] {
+        let mut game = builder.build().unwrap();
+        let mut response = None;
+        for guess in guesses {
+            response = Some(game.guess(&guess.parse().unwrap(), None).unwrap());
+        }
+        report.add_timed(response.unwrap(), chrono::TimeDelta::milliseconds(ms));
+    }
+
+    assert_eq!(report.avg_steps(), 1.5);
+    // the lost game counts as 3 guesses
+    assert_eq!(report.avg_score(), 2.0);
+    assert_eq!(report.median_steps(), 2.0);
+    assert_eq!(report.percentile_steps(100.0), 3);
+    assert!((report.std_dev_steps() - 2.0_f64.sqrt()).abs() < 1e-9);
+    let (low, high) = report.avg_score_interval();
+    assert!(((low + high) / 2.0 - 2.0).abs() < 1e-9);
+    assert_eq!(report.avg_time(), chrono::TimeDelta::milliseconds(20));
+}
+
+#[test]
+fn test_benchmark_report() {
+    let wl = small_wordlist();
+    let solver = NaiveSolver::build(&wl).unwrap();
+    let bench = BuiltinBenchmark::build(&wl, solver, Game::builder(&wl), 1).unwrap();
+    bench.run(&Targets::Random(20)).unwrap();
+    let report = bench.report();
+    assert!(report.finished());
+    assert_eq!(report.n(), 20);
+    assert_eq!(
+        report.distribution().iter().sum::<usize>(),
+        report.total_wins()
+    );
+    assert_eq!(report.slowest(usize::MAX).len(), 20);
+    let worst = report.worst(usize::MAX);
+    assert!(worst
+        .windows(2)
+        .all(|w| (w[0].won(), w[1].steps()) <= (w[1].won(), w[0].steps())));
+    assert!(worst.iter().all(|o| o.solution().is_some()));
+}
+
+#[test]
+fn test_report_export() {
+    let wl = small_wordlist();
+    let solver = NaiveSolver::build(&wl).unwrap();
+    let bench = BuiltinBenchmark::build(&wl, solver, Game::builder(&wl), 1).unwrap();
+    bench.run(&Targets::Random(10)).unwrap();
+    let report = bench.report();
+    assert_eq!(report.solver(), "NaiveSolver");
+    assert_eq!(report.wordlist(), wl.name());
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["solver"], "NaiveSolver");
+    assert_eq!(json["n"], 10);
+    assert_eq!(json["games"].as_array().unwrap().len(), 10);
+    assert_eq!(json["distribution"].as_array().unwrap().len(), 6);
+
+    let csv = report.to_csv().unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("solver,wordlist,solution,won,steps,time_us")
+    );
+    assert_eq!(lines.count(), 10);
+    let summary = report.summary_to_csv().unwrap();
+    assert_eq!(summary.lines().count(), 2);
+    assert!(summary.lines().nth(1).unwrap().starts_with("NaiveSolver,"));
+}
+
+#[test]
+fn test_benchmark_targets() {
+    let wl = small_wordlist();
+    let solver = NaiveSolver::build(&wl).unwrap();
+
+    let bench = BuiltinBenchmark::build(&wl, solver.clone(), Game::builder(&wl), 1).unwrap();
+    bench.run(&Targets::Exhaustive).unwrap();
+    let report = bench.report();
+    let mut solutions: Vec<_> = wl.solutions().into_iter().map(|s| s.0).collect();
+    solutions.sort();
+    let played: Vec<_> = report
+        .outcomes()
+        .iter()
+        .map(|o| o.solution().unwrap())
+        .collect();
+    assert_eq!(played, solutions);
+    assert_eq!(report.targets(), "all solutions");
+
+    let seeded = |seed| {
+        let bench = BuiltinBenchmark::build(&wl, solver.clone(), Game::builder(&wl), 1).unwrap();
+        bench.run(&Targets::Seeded { n: 10, seed }).unwrap();
+        bench
+            .report()
+            .outcomes()
+            .iter()
+            .map(|o| (o.solution(), o.steps()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(seeded(42), seeded(42));
+    assert_ne!(seeded(42), seeded(43));
+
+    let words = vec!["cater".parse().unwrap(), "blame".parse().unwrap()];
+    let bench = BuiltinBenchmark::build(&wl, solver.clone(), Game::builder(&wl), 1).unwrap();
+    bench.run(&Targets::Words(words.clone())).unwrap();
+    let played: Vec<_> = bench
+        .report()
+        .outcomes()
+        .iter()
+        .map(|o| o.solution().unwrap())
+        .collect();
+    assert_eq!(played, words);
+    assert!(Targets::Words(vec!["zzzzz".parse().unwrap()])
+        .solutions(&wl, 5, 1)
+        .is_err());
+    assert!(matches!(
+        Targets::Words(vec!["crane".parse().unwrap()]).solutions(&wl, 6, 1),
+        Err(Error::BenchError {
+            source: BenchError::TargetHasWrongLength(_, 6)
+        })
+    ));
+}
+
+#[test]
+fn test_comparison() {
+    let wl = small_wordlist();
+    let run = |solver: AnyBuiltinSolver<'_, BuiltinWList>, targets: &Targets| {
+        let bench = BuiltinBenchmark::build(&wl, solver, Game::builder(&wl), 1).unwrap();
+        bench.run(targets).unwrap();
+        bench.report()
+    };
+    let naive = run(
+        NaiveSolver::build(&wl).unwrap().into(),
+        &Targets::Exhaustive,
+    );
+    let entropy = run(
+        EntropySolver::build(&wl).unwrap().into(),
+        &Targets::Exhaustive,
+    );
+
+    let comparison = Comparison::new(vec![naive.clone(), entropy]).unwrap();
+    let pair = comparison.paired(1);
+    assert_eq!(pair.better() + pair.worse() + pair.ties(), naive.n());
+    assert_eq!(pair.differences().len(), naive.n());
+    let differing = pair
+        .differences()
+        .iter()
+        .find(|d| d.delta() != 0)
+        .and_then(|d| d.solution)
+        .expect("the solvers play some game differently");
+
+    let itself = Comparison::new(vec![naive.clone(), naive.clone()]).unwrap();
+    let pair = itself.paired(1);
+    assert_eq!(pair.ties(), naive.n());
+    assert_eq!(pair.mean_difference(), 0.0);
+    assert_eq!(pair.t_statistic(), Some(0.0));
+    assert!(!pair.significant());
+
+    // the differences of a single game do not vary, so there is no t-statistic
+    let one = Targets::Words(vec![differing]);
+    let single = Comparison::new(vec![
+        run(NaiveSolver::build(&wl).unwrap().into(), &one),
+        run(EntropySolver::build(&wl).unwrap().into(), &one),
+    ])
+    .unwrap();
+    let pair = single.paired(1);
+    assert_ne!(pair.mean_difference(), 0.0);
+    assert_eq!(pair.std_dev(), 0.0);
+    assert_eq!(pair.t_statistic(), None);
+    assert!(!pair.significant());
+    assert!(!single.to_string().contains("NaN") && !single.to_string().contains("inf"));
+
+    let other = run(
+        NaiveSolver::build(&wl).unwrap().into(),
+        &Targets::Seeded { n: 10, seed: 1 },
+    );
+    assert!(Comparison::new(vec![naive, other]).is_err());
+    assert!(Comparison::new(vec![]).is_err());
+}
+
+#[test]
+fn test_benchmark_solver_error() {
+    let wl = small_wordlist();
+    // the tree can not be cached in a directory that is a file, so every game fails
+    let file = std::env::temp_dir().join(format!("wordle-bench-error-{}", std::process::id()));
+    std::fs::write(&file, "").unwrap();
+    let solver = DecisionTreeSolver::build(&wl).unwrap().cache_dir(&file);
+    let bench = BuiltinBenchmark::build(&wl, solver, Game::builder(&wl), 1).unwrap();
+    assert!(bench.run(&Targets::Random(5)).is_err());
+    assert!(bench.is_finished());
+    assert!(!bench.report().finished());
+    std::fs::remove_file(&file).unwrap();
+}
+
+#[test]
+fn test_benchmark_background() {
+    let wl = small_wordlist();
+    let solver = NaiveSolver::build(&wl).unwrap();
+    let bench = BuiltinBenchmark::build(&wl, solver, Game::builder(&wl), 2).unwrap();
+
+    let report = std::thread::scope(|s| {
+        let handle = bench.start_targets(s, &Targets::Exhaustive).unwrap();
+        while !handle.is_finished() {
+            let progress = bench.progress();
+            assert!(progress.done() <= progress.total());
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        handle.join().unwrap().unwrap()
+    });
+    assert!(bench.is_finished());
+    assert!(!report.cancelled());
+    assert_eq!(report.n(), wl.solutions().len());
+    let progress = bench.progress();
+    assert!(progress.finished());
+    assert_eq!(progress.done(), progress.total());
+    assert_eq!(progress.fraction(), 1.0);
+    assert_eq!(progress.eta(), Some(chrono::TimeDelta::zero()));
+
+    // cancelled before a game is started
+    let report = std::thread::scope(|s| {
+        let handle = bench.start_targets(s, &Targets::Exhaustive).unwrap();
+        bench.cancel().unwrap();
+        handle.join().unwrap().unwrap()
+    });
+    assert!(report.finished());
+    assert!(report.cancelled());
+    assert_eq!(report.n(), 0);
+    assert_eq!(report.total(), wl.solutions().len());
+    assert!(bench.progress().cancelled());
+}
+
+#[test]
+fn test_baseline() {
+    let wl = small_wordlist();
+    let targets = Targets::Exhaustive;
+    let entropy = BuiltinBenchmark::build(
+        &wl,
+        EntropySolver::build(&wl).unwrap(),
+        Game::builder(&wl),
+        1,
+    )
+    .unwrap()
+    .run(&targets)
+    .unwrap();
+    let naive =
+        BuiltinBenchmark::build(&wl, NaiveSolver::build(&wl).unwrap(), Game::builder(&wl), 1)
+            .unwrap()
+            .run(&targets)
+            .unwrap();
+
+    let path = std::env::temp_dir().join(format!("wordle-baseline-{}.json", std::process::id()));
+    Baseline::of("entropy", &entropy).save(&path).unwrap();
+    let baseline = Baseline::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(baseline.name(), "entropy");
+    assert_eq!(baseline.summary(), &entropy.summary());
+
+    let check = baseline.check(&entropy, &Tolerance::default());
+    assert!(check.passed());
+    assert!(check.mismatches().is_empty());
+
+    let check = baseline.check(&naive, &Tolerance::default());
+    assert_eq!(check.mismatches().len(), 1);
+    if naive.avg_steps() > entropy.avg_steps() + Tolerance::default().avg_steps {
+        assert!(!check.passed());
+        assert!(check.into_result().is_err());
+    }
+    let lenient = Tolerance {
+        avg_steps: f64::INFINITY,
+        win_ratio: 1.0,
+    };
+    assert!(baseline.check(&naive, &lenient).into_result().is_ok());
+}
+
+#[test]
+fn test_benchmark_word_lengths() {
+    let wl = BuiltinWList::german(6);
+    let solver = EntropySolver::build(&wl).unwrap();
+    let builder = Game::builder(&wl).length(6).max_steps(30);
+    let bench = BuiltinBenchmark::build(&wl, solver, builder, 1).unwrap();
+    let report = bench.run(&Targets::Seeded { n: 5, seed: 6 }).unwrap();
+    assert_eq!(report.summary().length, 6);
+    assert_eq!(report.summary().n, 5);
+    assert!(report
+        .outcomes()
+        .iter()
+        .all(|o| o.won() && o.solution().unwrap().char_len() == 6));
+
+    let wl = BuiltinWList::english(5);
+    let solver = NaiveSolver::build(&wl).unwrap();
+    assert!(BuiltinBenchmark::build(&wl, solver, Game::builder(&wl).length(7), 1).is_err());
+}
+
+#[test]
+fn test_entropy_beats_naive() {
+    let wl = BuiltinWList::default();
+    let targets = Targets::Seeded { n: 40, seed: 3 };
+    let naive = BuiltinBenchmark::build(
+        &wl,
+        NaiveSolver::build(&wl).unwrap(),
+        Game::builder(&wl).max_steps(30),
+        1,
+    )
+    .unwrap();
+    let entropy = BuiltinBenchmark::build(
+        &wl,
+        EntropySolver::build(&wl).unwrap(),
+        Game::builder(&wl).max_steps(30),
+        1,
+    )
+    .unwrap();
+    let naive = naive.run(&targets).unwrap().summary();
+    let entropy = entropy.run(&targets).unwrap().summary();
+    assert!(
+        entropy.avg_steps < naive.avg_steps,
+        "entropy {} vs naive {}",
+        entropy.avg_steps,
+        naive.avg_steps
+    );
+}
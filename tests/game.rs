@@ -0,0 +1,611 @@
+use test_log::test; // set the log level with an envvar: `RUST_LOG=trace cargo test`
+
+use wordle_analyzer::error::{Error, GameError};
+use wordle_analyzer::game::constraints::Constraints;
+use wordle_analyzer::game::evaluation::{pattern, Evaluation};
+use wordle_analyzer::game::keyboard::LetterState;
+use wordle_analyzer::game::matrix::PatternMatrix;
+use wordle_analyzer::game::record::GameRecord;
+use wordle_analyzer::game::share::ShareGrid;
+use wordle_analyzer::game::Game;
+use wordle_analyzer::wlist::builtin::BuiltinWList;
+use wordle_analyzer::wlist::word::{Word, WordData, WordMap};
+use wordle_analyzer::wlist::WordList;
+
+fn wordlist() -> impl WordList {
+    BuiltinWList::default()
+}
+
+/// the words of multiple wordlists, which may have different lengths
+#[derive(Debug, Clone, Default)]
+struct MixedWList {
+    words: WordMap,
+}
+
+impl MixedWList {
+    fn of(lists: &[BuiltinWList]) -> Self {
+        let mut words = WordMap::new();
+        for wl in lists {
+            words
+                .inner_mut()
+                .extend(wl.wordmap().iter().map(|(w, f)| (*w, *f)));
+        }
+        Self { words }
+    }
+}
+
+impl std::fmt::Display for MixedWList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl WordList for MixedWList {
+    fn name(&self) -> &str {
+        "(mixed lengths)"
+    }
+    fn wordmap(&self) -> &WordMap {
+        &self.words
+    }
+    fn get_word(&self, word: &Word) -> Option<WordData> {
+        self.words.inner().get(word).map(|f| (*word, *f))
+    }
+    /// every word can be the solution
+    fn solutions(&self) -> Vec<WordData> {
+        self.words.iter().map(|(w, f)| (*w, *f)).collect()
+    }
+}
+
+#[test]
+fn test_eval_simple() {
+    let wl = wordlist();
+    let builder = Game::builder(&wl).solution(wl.get_word(&"crate".parse().unwrap()));
+    let mut game = builder.build().unwrap();
+    let response = game.guess(&"trace".parse().unwrap(), None).unwrap();
+    assert_eq!(
+        *response.evaluation(),
+        Evaluation::build(&"trace".parse().unwrap(), "fccfc").unwrap()
+    );
+}
+
+#[test]
+fn test_eval_repeated_letters() {
+    let wl = wordlist();
+    let builder = Game::builder(&wl).solution(wl.get_word(&"crane".parse().unwrap()));
+    let mut game = builder.build().unwrap();
+    // "crane" has only one a and one r, the r is already matched
+    let response = game.guess(&"array".parse().unwrap(), None).unwrap();
+    assert_eq!(
+        *response.evaluation(),
+        Evaluation::build(&"array".parse().unwrap(), "fcxxx").unwrap()
+    );
+    assert_eq!(
+        Evaluation::from_pattern(&"array".parse().unwrap(), pattern("crane", "array")),
+        Evaluation::build(&"array".parse().unwrap(), "fcxxx").unwrap()
+    );
+}
+
+#[test]
+fn test_pattern_roundtrip() {
+    let guess: Word = "eerie".parse().unwrap();
+    let eval = Evaluation::build(&guess, "cfxxc").unwrap();
+    assert_eq!(Evaluation::from_pattern(&guess, eval.pattern()), eval);
+    assert_eq!(Evaluation::build(&guess, "ccccc").unwrap().pattern(), 242);
+}
+
+fn small_wordlist() -> BuiltinWList {
+    BuiltinWList::load("data/wordlists/test_small.json", 5).unwrap()
+}
+
+#[test]
+fn test_pattern_matrix() {
+    let wl = small_wordlist();
+    let matrix = PatternMatrix::build(&wl, 5).unwrap();
+    assert_eq!(matrix.guesses().len(), wl.wordmap().len());
+    assert_eq!(matrix.solutions().len(), wl.solutions().len());
+    for guess in matrix.guesses() {
+        for solution in matrix.solutions() {
+            assert_eq!(matrix.get(guess, solution), Some(pattern(solution, guess)));
+        }
+    }
+    assert!(PatternMatrix::build(&wl, 11).is_err());
+}
+
+#[test]
+fn test_pattern_matrix_cache() {
+    let wl = small_wordlist();
+    let dir = std::env::temp_dir().join("wordle-analyzer-test-pattern-cache");
+    let _ = std::fs::remove_dir_all(&dir);
+    let matrix = PatternMatrix::cached(&wl, 5, &dir).unwrap();
+    let path = PatternMatrix::cache_path(&wl, 5, &dir);
+    assert!(path.exists());
+    assert_eq!(PatternMatrix::load(&path).unwrap(), matrix);
+    assert_eq!(PatternMatrix::cached(&wl, 5, &dir).unwrap(), matrix);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_eval_precomputed() {
+    let wl = wordlist();
+    let builder = Game::builder(&wl)
+        .precompute(true)
+        .solution(wl.get_word(&"crane".parse().unwrap()));
+    let mut game = builder.build().unwrap();
+    assert!(game.patterns().is_some());
+    let response = game.guess(&"array".parse().unwrap(), None).unwrap();
+    assert_eq!(
+        *response.evaluation(),
+        Evaluation::build(&"array".parse().unwrap(), "fcxxx").unwrap()
+    );
+}
+
+#[test]
+fn test_hard_mode() {
+    let wl = wordlist();
+    let builder = Game::builder(&wl)
+        .hard_mode(true)
+        .solution(wl.get_word(&"crane".parse().unwrap()));
+    let mut game = builder.build().unwrap();
+    // c and r are matched, e exists
+    game.guess(&"cruel".parse().unwrap(), None).unwrap();
+    match game.guess(&"trace".parse().unwrap(), None) {
+        Err(GameError::HardModeViolation(msg)) => assert_eq!(msg, "1st letter must be C"),
+        other => panic!("expected a hard mode violation, got {other:?}"),
+    }
+    match game.guess(&"craft".parse().unwrap(), None) {
+        Err(GameError::HardModeViolation(msg)) => assert_eq!(msg, "Guess must contain E"),
+        other => panic!("expected a hard mode violation, got {other:?}"),
+    }
+    assert_eq!(game.step(), 1);
+    assert!(game.allows(&"crate".parse().unwrap()));
+    assert!(game.guess(&"crate".parse().unwrap(), None).is_ok());
+    assert!(game.guess(&"crane".parse().unwrap(), None).unwrap().won());
+}
+
+#[test]
+fn test_hard_mode_disabled() {
+    let wl = wordlist();
+    let builder = Game::builder(&wl).solution(wl.get_word(&"crane".parse().unwrap()));
+    let mut game = builder.build().unwrap();
+    game.guess(&"cruel".parse().unwrap(), None).unwrap();
+    assert!(game.check_hard_mode(&"trace".parse().unwrap()).is_err());
+    assert!(game.allows(&"trace".parse().unwrap()));
+    assert!(game.guess(&"trace".parse().unwrap(), None).is_ok());
+}
+
+#[test]
+fn test_adversarial() {
+    let wl = small_wordlist();
+    let builder = Game::builder(&wl).adversarial(true).max_steps(30);
+    let mut game = builder.build().unwrap();
+    assert!(game.solution().is_none());
+    assert_eq!(game.candidates().len(), wl.solutions().len());
+    let response = game.guess(&"crane".parse().unwrap(), None).unwrap();
+    assert!(!response.won());
+    let left = game.candidates().to_vec();
+    assert!(left.len() > 1);
+    for candidate in &left {
+        assert_eq!(
+            pattern(&candidate.0, "crane"),
+            response.evaluation().pattern()
+        );
+    }
+    game.guess(&"champ".parse().unwrap(), None).unwrap();
+    game.undo(1).unwrap();
+    assert_eq!(game.candidates(), left.as_slice());
+    while !game.finished() {
+        let next = game.candidates()[0].0;
+        game.guess(&next, None).unwrap();
+    }
+    assert!(game.won());
+    assert_eq!(game.candidates().len(), 1);
+    assert_eq!(game.solution(), Some(&game.candidates()[0]));
+}
+
+#[test]
+fn test_multi_game() {
+    let wl = small_wordlist();
+    let builder = Game::builder(&wl);
+    assert!(builder.build_multi(0).is_err());
+    let mut game = builder.build_multi(4).unwrap();
+    assert_eq!(game.max_steps(), wordle_analyzer::DEFAULT_MAX_STEPS + 3);
+    for (idx, solution) in ["crane", "slate", "fable", "grape"].iter().enumerate() {
+        game.set_solution(idx, wl.get_word(&solution.parse().unwrap()));
+    }
+    let responses = game.guess(&"slate".parse().unwrap()).unwrap();
+    assert_eq!(responses.len(), 4);
+    assert!(responses[1].as_ref().unwrap().won());
+    assert_eq!(game.solved(), 1);
+    let responses = game.guess(&"crane".parse().unwrap()).unwrap();
+    assert!(responses[1].is_none());
+    assert!(responses[0].as_ref().unwrap().won());
+    assert_eq!(game.boards()[1].responses().len(), 1);
+    assert_eq!(game.unsolved().count(), 2);
+
+    game.undo(1).unwrap();
+    assert_eq!(game.step(), 1);
+    assert_eq!(game.solved(), 1);
+    for word in ["crane", "fable", "grape"] {
+        game.guess(&word.parse().unwrap()).unwrap();
+    }
+    assert!(game.won());
+    assert!(game.finished());
+    assert_eq!(game.step(), 4);
+    assert!(game.guess(&"crane".parse().unwrap()).is_err());
+}
+
+#[test]
+fn test_multi_game_distinct_solutions() {
+    let wl = small_wordlist();
+    let builder = Game::builder(&wl);
+    let game = builder.build_multi(8).unwrap();
+    let mut solutions: Vec<_> = game
+        .boards()
+        .iter()
+        .map(|b| b.solution().unwrap().0)
+        .collect();
+    solutions.sort();
+    solutions.dedup();
+    assert_eq!(solutions.len(), 8);
+}
+
+#[test]
+fn test_constraints_repeated_letters() {
+    let mut constraints = Constraints::new(5);
+    // "crane" has one r, so the second r of "error" tells us there is only one
+    constraints.add(&"error".parse().unwrap(), pattern("crane", "error"));
+    assert_eq!(constraints.occurrences('r'), Some((1, 1)));
+    assert!(constraints.contains('e'));
+    assert!(constraints.excludes('o'));
+    assert!(constraints.contains('r'));
+    assert!(constraints.matches("crane"));
+    assert!(!constraints.matches("rarer"));
+    assert!(!constraints.matches("cran"));
+}
+
+#[test]
+fn test_constraints_exact() {
+    let wl = wordlist();
+    let words: Vec<Word> = wl.wordmap().keys().copied().collect();
+    let solution: Word = "sheep".parse().unwrap();
+    let mut constraints = Constraints::new(5);
+    let mut candidates = wl.words_matching(&constraints);
+    assert_eq!(candidates.len(), wl.wordmap().len());
+    let mut guesses: Vec<Word> = Vec::new();
+    for guess in ["eerie", "speed", "geese"] {
+        let guess: Word = guess.parse().unwrap();
+        guesses.push(guess);
+        constraints.add(&guess, pattern(&solution, &guess));
+        constraints.retain(&mut candidates);
+        // the words that fit are exactly those for which all guesses get the same patterns
+        for w in &words {
+            let same = guesses
+                .iter()
+                .all(|g| pattern(w, g) == pattern(&solution, g));
+            assert_eq!(constraints.matches(w), same, "{w}");
+        }
+        assert!(candidates.iter().any(|c| c.0 == solution));
+        assert_eq!(candidates, wl.words_matching(&constraints));
+    }
+}
+
+#[test]
+fn test_game_constraints() {
+    let wl = wordlist();
+    let mut game = Game::builder(&wl)
+        .solution(wl.get_word(&"crane".parse().unwrap()))
+        .build()
+        .unwrap();
+    game.guess(&"trace".parse().unwrap(), None).unwrap();
+    game.guess(&"brace".parse().unwrap(), None).unwrap();
+    assert_eq!(
+        *game.constraints(),
+        Constraints::from_responses(5, game.responses())
+    );
+    assert_eq!(game.constraints().known()[1], Some('r'));
+    game.undo(1).unwrap();
+    assert_eq!(
+        *game.constraints(),
+        Constraints::from_responses(5, game.responses())
+    );
+    game.undo(1).unwrap();
+    assert!(game.constraints().is_empty());
+}
+
+#[test]
+fn test_max_steps() {
+    let wl = wordlist();
+    let mut game = Game::builder(&wl)
+        .max_steps(2)
+        .solution(wl.get_word(&"crane".parse().unwrap()))
+        .build()
+        .unwrap();
+    game.guess(&"sloth".parse().unwrap(), None).unwrap();
+    assert!(!game.finished());
+    game.guess(&"pudgy".parse().unwrap(), None).unwrap();
+    assert!(game.finished());
+    assert!(!game.won());
+    assert!(matches!(
+        game.guess(&"crane".parse().unwrap(), None),
+        Err(GameError::TryingToPlayAFinishedGame)
+    ));
+}
+
+#[test]
+fn test_keyboard() {
+    let wl = wordlist();
+    let mut game = Game::builder(&wl)
+        .solution(wl.get_word(&"crane".parse().unwrap()))
+        .build()
+        .unwrap();
+    game.guess(&"error".parse().unwrap(), None).unwrap();
+    let keyboard = game.keyboard();
+    assert_eq!(keyboard.state('e'), LetterState::Present);
+    assert_eq!(keyboard.state('r'), LetterState::Confirmed);
+    assert_eq!(keyboard.state('o'), LetterState::Absent);
+    assert_eq!(keyboard.state('c'), LetterState::Unknown);
+    let r = keyboard.get('r');
+    assert_eq!(r.confirmed_at(), [1]);
+    assert_eq!(r.excluded_at(), [2, 4]);
+    assert_eq!((r.min_count(), r.max_count()), (1, Some(1)));
+    assert_eq!(keyboard.get('e').excluded_at(), [0]);
+    assert_eq!(
+        keyboard.with_state(LetterState::Absent).collect::<Vec<_>>(),
+        ['o']
+    );
+    game.undo(1).unwrap();
+    assert_eq!(game.keyboard().state('r'), LetterState::Unknown);
+    assert_eq!(game.keyboard().letters().count(), 0);
+}
+
+#[test]
+fn test_record_roundtrip() {
+    let wl = wordlist();
+    let mut game = Game::builder(&wl)
+        .hard_mode(true)
+        .solution(wl.get_word(&"crane".parse().unwrap()))
+        .build()
+        .unwrap();
+    game.guess(&"slate".parse().unwrap(), None).unwrap();
+    game.guess(&"crane".parse().unwrap(), None).unwrap();
+    let record = game.record();
+    assert_eq!(record.guesses().len(), 2);
+    assert_eq!(record.guesses()[0].to_string(), "slate xxcxc");
+
+    let text = record.to_string();
+    assert!(text.starts_with("#wordle-analyzer v1 length=5 max_steps=6 hard_mode=true"));
+    assert_eq!(text.parse::<GameRecord>().unwrap(), record);
+    assert_eq!(
+        GameRecord::from_json(&record.to_json().unwrap()).unwrap(),
+        record
+    );
+
+    let replayed = record.to_game(&wl).unwrap();
+    assert!(replayed.won());
+    assert_eq!(replayed.responses(), game.responses());
+}
+
+#[test]
+fn test_replay_step_by_step() {
+    let wl = wordlist();
+    // a game played elsewhere, the solution is not known
+    let record: GameRecord = "#wordle-analyzer v1 length=5 max_steps=6 wordlist=anything\n\
+        slate xxcxc\n\
+        brace xccpc"
+        .parse()
+        .unwrap();
+    assert_eq!(record.solution(), None);
+    let mut replay = record.replay(&wl).unwrap();
+    assert_eq!(replay.remaining(), 2);
+    let first = replay.next().unwrap().unwrap();
+    assert_eq!(first.guess().as_str(), "slate");
+    assert_eq!(replay.game().responses().len(), 1);
+    replay.next().unwrap().unwrap();
+    assert!(replay.next().is_none());
+    assert!(replay.game().constraints().matches("crane"));
+}
+
+#[test]
+fn test_bad_record() {
+    let wl = wordlist();
+    // crane does not give this evaluation for slate
+    let record: GameRecord =
+        "#wordle-analyzer v1 length=5 max_steps=6 solution=crane wordlist=x\nslate ccccc"
+            .parse()
+            .unwrap();
+    assert!(record.to_game(&wl).is_err());
+    assert!("#wordle-analyzer v2 length=5 max_steps=6 wordlist=x"
+        .parse::<GameRecord>()
+        .is_err());
+    assert!(
+        "#wordle-analyzer v1 length=5 max_steps=6 wordlist=x\nslate xxc"
+            .parse::<GameRecord>()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_share_grid() {
+    let wl = wordlist();
+    let mut game = Game::builder(&wl)
+        .solution(wl.get_word(&"crane".parse().unwrap()))
+        .build()
+        .unwrap();
+    game.guess(&"slate".parse().unwrap(), None).unwrap();
+    game.guess(&"brace".parse().unwrap(), None).unwrap();
+    game.guess(&"crane".parse().unwrap(), None).unwrap();
+    let grid = game.share().with_puzzle("1,234");
+    let text = grid.to_string();
+    assert_eq!(
+        text,
+        "Wordle 1,234 3/6\n\n⬛⬛🟩⬛🟩\n⬛🟩🟩🟨🟩\n🟩🟩🟩🟩🟩"
+    );
+    assert_eq!(text.parse::<ShareGrid>().unwrap(), grid);
+
+    // light mode, hard mode, a lost game and a link after the grid
+    let lost: ShareGrid = "Wordle 1,235 X/6*\n\n⬜⬜⬜⬜⬜\n⬜⬜🟨⬜⬜\nhttps://example.com"
+        .parse()
+        .unwrap();
+    assert!(!lost.won());
+    assert!(lost.hard_mode());
+    assert_eq!(lost.puzzle(), Some("1,235"));
+    assert_eq!(lost.rows(), [0, 9]);
+    assert!("Wordle 1,234 2/6\n\n⬛⬛🟩⬛🟩"
+        .parse::<ShareGrid>()
+        .is_err());
+    assert!("Wordle 1,234 2/6\n\n⬛⬛🟩⬛🟩\n🟩🟩🟩🟩"
+        .parse::<ShareGrid>()
+        .is_err());
+}
+
+#[test]
+fn test_share_grid_import() {
+    let wl = wordlist();
+    let solution: Word = "crane".parse().unwrap();
+    let grid: ShareGrid = "Wordle 1,234 3/6\n\n⬛⬛🟩⬛🟩\n⬛🟩🟩🟨🟩\n🟩🟩🟩🟩🟩"
+        .parse()
+        .unwrap();
+    let candidates = grid.candidates(&wl, &solution);
+    assert_eq!(candidates.len(), 3);
+    assert!(candidates[0].iter().any(|w| w.0.as_str() == "slate"));
+    assert!(candidates[1].iter().any(|w| w.0.as_str() == "brace"));
+    assert_eq!(candidates[2].len(), 1);
+
+    let game = grid.to_game(&wl, &solution).unwrap();
+    assert!(game.won());
+    assert_eq!(game.share().with_puzzle("1,234"), grid);
+    let evaluations = grid
+        .evaluations(&["slate".parse().unwrap(), "brace".parse().unwrap(), solution])
+        .unwrap();
+    assert_eq!(evaluations[1].to_eval_string(), "xccpc");
+
+    // no word can have the last letter of crane somewhere else
+    let grid: ShareGrid = "Wordle 1 X/6\n\n🟩🟩🟩🟩🟨".parse().unwrap();
+    assert!(grid.candidates(&wl, &solution)[0].is_empty());
+    assert!(grid.to_game(&wl, &solution).is_err());
+}
+
+#[test]
+fn test_share_grid_import_backtracks() {
+    let dir = std::env::temp_dir().join(format!("wordle-share-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("share.json");
+    std::fs::write(
+        &path,
+        r#"{"crane": 0.5, "night": 0.9, "rusty": 0.1, "scrap": 0.5}"#,
+    )
+    .unwrap();
+    let wl = BuiltinWList::load(&path, 5).unwrap();
+    let solution: Word = "crane".parse().unwrap();
+    // night and rusty both fit the first row, but in hard mode only rusty can be followed by scrap
+    let grid: ShareGrid = "Wordle 1 3/6*\n\n🟨⬛⬛⬛⬛\n⬛🟨🟨🟨⬛\n🟩🟩🟩🟩🟩"
+        .parse()
+        .unwrap();
+    assert_eq!(grid.candidates(&wl, &solution)[0][0].0.as_str(), "night");
+    let game = grid.to_game(&wl, &solution).unwrap();
+    let guesses: Vec<&str> = game
+        .responses()
+        .iter()
+        .map(|r| r.guess().as_str())
+        .collect();
+    assert_eq!(guesses, ["rusty", "scrap", "crane"]);
+    assert_eq!(game.share().with_puzzle("1"), grid);
+
+    // without rusty, no word fits the second row after the first one
+    std::fs::write(&path, r#"{"crane": 0.5, "night": 0.9, "scrap": 0.5}"#).unwrap();
+    let wl = BuiltinWList::load(&path, 5).unwrap();
+    assert!(matches!(
+        grid.to_game(&wl, &solution),
+        Err(Error::GameError {
+            source: GameError::NoFeasibleGuess(2)
+        })
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_share_grid_header() {
+    // other words that start with "Wordle" are not the header
+    let grid: ShareGrid = "Wordlebot 3/6\n\n⬛⬛🟩⬛🟩\n🟩🟩🟩🟩🟩".parse().unwrap();
+    assert_eq!(grid.max_steps(), 6);
+    assert_eq!(grid.puzzle(), None);
+    assert!(grid.won());
+    let grid: ShareGrid = "Wordlebot 3/6\nWordle 7 2/4\n\n⬛⬛🟩⬛🟩\n🟩🟩🟩🟩🟩"
+        .parse()
+        .unwrap();
+    assert_eq!(grid.puzzle(), Some("7"));
+    assert_eq!(grid.max_steps(), 4);
+    assert!("Wordle\n\n🟩🟩🟩🟩🟩".parse::<ShareGrid>().is_err());
+}
+
+#[test]
+fn test_german_game() {
+    let wl = BuiltinWList::german(5);
+    let builder = Game::builder(&wl).solution(wl.get_word(&"würde".parse().unwrap()));
+    let mut game = builder.build().unwrap();
+    let guess: Word = "hören".parse().unwrap();
+    let response = game.guess(&guess, None).unwrap();
+    assert_eq!(
+        *response.evaluation(),
+        Evaluation::build(&guess, "xxcpx").unwrap()
+    );
+    let guess: Word = "Wurde".parse().unwrap();
+    let response = game.guess(&guess, None).unwrap();
+    assert_eq!(
+        *response.evaluation(),
+        Evaluation::build(&guess, "cxccc").unwrap()
+    );
+    assert!(matches!(
+        game.guess(&"würden".parse().unwrap(), None),
+        Err(GameError::GuessHasWrongLength(6))
+    ));
+    assert!(game.guess(&"WÜRDE".parse().unwrap(), None).unwrap().won());
+}
+
+#[test]
+fn test_word_lengths() {
+    let wl = BuiltinWList::german(7);
+    assert_eq!(wl.length_range(), 7..=7);
+    let mut game = Game::builder(&wl).length(7).build().unwrap();
+    assert_eq!(game.solution().unwrap().0.char_len(), 7);
+    assert!(game.guess(&"zwischen".parse().unwrap(), None).is_err());
+    assert!(game.guess(&"abcdefg".parse().unwrap(), None).is_err());
+    assert!(game.guess(&"niemand".parse().unwrap(), None).is_ok());
+
+    // the english list only has words with 5 letters
+    let wl = BuiltinWList::english(5);
+    assert_eq!(wl.length_range(), 5..=5);
+    assert!(matches!(
+        Game::builder(&wl).length(6).build(),
+        Err(GameError::NoWordsWithLength(6))
+    ));
+    let wl = BuiltinWList::german(2);
+    assert!(matches!(
+        Game::builder(&wl).length(2).build(),
+        Err(GameError::UnsupportedLength(2))
+    ));
+    let wl = BuiltinWList::german(13);
+    assert!(wl.length_range().is_empty());
+    assert!(Game::builder(&wl).length(13).build().is_err());
+}
+
+#[test]
+fn test_word_lengths_with_gap() {
+    let wl = MixedWList::of(&[BuiltinWList::german(4), BuiltinWList::german(6)]);
+    assert_eq!(wl.lengths().into_iter().collect::<Vec<_>>(), [4, 6]);
+    assert_eq!(wl.length_range(), 4..=6);
+    assert!(wl.has_length(4) && wl.has_length(6));
+    assert!(!wl.has_length(5));
+    assert!(matches!(
+        Game::builder(&wl).length(5).build(),
+        Err(GameError::NoWordsWithLength(5))
+    ));
+    assert!(wl.rand_solution(5).is_none());
+    for _ in 0..50 {
+        for length in [4, 6] {
+            let game = Game::builder(&wl).length(length).build().unwrap();
+            assert_eq!(game.solution().unwrap().0.char_len(), length);
+            assert_eq!(wl.rand_solution(length).unwrap().0.char_len(), length);
+        }
+    }
+}
@@ -1,13 +1,64 @@
 use test_log::test; // set the log level with an envvar: `RUST_LOG=trace cargo test`
 
-use wordle_analyzer::solve::{AnyBuiltinSolver, NaiveSolver, Solver, StupidSolver};
+use wordle_analyzer::game::evaluation::Evaluation;
+use wordle_analyzer::game::{Game, GameBuilder};
+use wordle_analyzer::solve::analysis::Analysis;
+use wordle_analyzer::solve::tree::{DecisionTree, Objective, TreeConfig};
+use wordle_analyzer::solve::{
+    AnyBuiltinSolver, DecisionTreeSolver, EntropySolver, NaiveSolver, Session, Solver, StupidSolver,
+};
 use wordle_analyzer::wlist::builtin::BuiltinWList;
+use wordle_analyzer::wlist::word::{Word, WordData, WordMap};
 use wordle_analyzer::wlist::WordList;
 
 fn wordlist() -> impl WordList {
     BuiltinWList::default()
 }
 
+/// the words of multiple wordlists, which may have different lengths
+#[derive(Debug, Clone, Default)]
+struct MixedWList {
+    words: WordMap,
+}
+
+impl MixedWList {
+    fn of(lists: &[BuiltinWList]) -> Self {
+        let mut words = WordMap::new();
+        for wl in lists {
+            words
+                .inner_mut()
+                .extend(wl.wordmap().iter().map(|(w, f)| (*w, *f)));
+        }
+        Self { words }
+    }
+}
+
+impl std::fmt::Display for MixedWList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl WordList for MixedWList {
+    fn name(&self) -> &str {
+        "(mixed lengths)"
+    }
+    fn wordmap(&self) -> &WordMap {
+        &self.words
+    }
+    fn get_word(&self, word: &Word) -> Option<WordData> {
+        self.words.inner().get(word).map(|f| (*word, *f))
+    }
+    /// every word can be the solution
+    fn solutions(&self) -> Vec<WordData> {
+        self.words.iter().map(|(w, f)| (*w, *f)).collect()
+    }
+}
+
+fn small_wordlist() -> BuiltinWList {
+    BuiltinWList::load("data/wordlists/test_small.json", 5).unwrap()
+}
+
 #[test]
 fn test_build_builtin_solvers() {
     let wl = wordlist();
@@ -15,4 +66,429 @@ fn test_build_builtin_solvers() {
         AnyBuiltinSolver::Stupid(StupidSolver::build(&wl).expect("could not build naive solver"));
     let _naive_solver =
         AnyBuiltinSolver::Naive(NaiveSolver::build(&wl).expect("could not build naive solver"));
+    let _entropy_solver = AnyBuiltinSolver::Entropy(
+        EntropySolver::build(&wl).expect("could not build entropy solver"),
+    );
+    let _tree_solver = AnyBuiltinSolver::DecisionTree(
+        DecisionTreeSolver::build(&wl).expect("could not build decision tree solver"),
+    );
+}
+
+#[test]
+fn test_naive_solver_repeated_letters() {
+    let wl = wordlist();
+    let solver = NaiveSolver::build(&wl).unwrap();
+    for solution in ["match", "mamma", "eerie"] {
+        let builder = Game::builder(&wl).solution(wl.get_word(&solution.parse().unwrap()));
+        let mut game = builder.build().unwrap();
+        let response = solver.play(&mut game).unwrap();
+        assert!(response.won(), "naive solver did not find {solution}");
+    }
+}
+
+#[test]
+fn test_entropy_solver_wins() {
+    let wl = wordlist();
+    let solver = EntropySolver::build(&wl).unwrap();
+    for solution in ["crane", "mamma", "which"] {
+        let builder = Game::builder(&wl).solution(wl.get_word(&solution.parse().unwrap()));
+        let mut game = builder.build().unwrap();
+        let response = solver.play(&mut game).unwrap();
+        assert!(response.won(), "entropy solver did not find {solution}");
+        assert!(game.responses().len() <= 5);
+    }
+}
+
+#[test]
+fn test_solvers_play_other_lengths() {
+    for length in [3, 4, 6, 7] {
+        let wl = BuiltinWList::german(length);
+        let solvers: [AnyBuiltinSolver<_>; 2] = [
+            NaiveSolver::build(&wl).unwrap().into(),
+            EntropySolver::build(&wl).unwrap().into(),
+        ];
+        for solver in solvers {
+            for solution in wl.solutions().into_iter().take(3) {
+                let builder = Game::builder(&wl)
+                    .length(length)
+                    .max_steps(30)
+                    .solution(Some(solution));
+                let mut game = builder.build().unwrap();
+                let response = solver.play(&mut game).unwrap();
+                assert!(response.won(), "{solver:?} did not find {}", solution.0);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_solvers_play_long_words() {
+    let dir = std::env::temp_dir().join(format!("wordle-lengths-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("long.txt");
+    std::fs::write(
+        &path,
+        "abbreviation acceleration accomplished championship headquarters intelligence \
+         manufacturer neighborhood photographer relationship surveillance construction",
+    )
+    .unwrap();
+    let wl = BuiltinWList::load(&path, 12).unwrap();
+    assert_eq!(wl.wordmap().len(), 12);
+    let solvers: [AnyBuiltinSolver<_>; 4] = [
+        StupidSolver::build(&wl).unwrap().into(),
+        NaiveSolver::build(&wl).unwrap().into(),
+        EntropySolver::build(&wl).unwrap().into(),
+        DecisionTreeSolver::build(&wl).unwrap().into(),
+    ];
+    for solver in solvers {
+        for solution in wl.solutions() {
+            // the stupid solver guesses at random and may repeat itself, give it plenty of steps
+            let builder = Game::builder(&wl)
+                .length(12)
+                .max_steps(1000)
+                .solution(Some(solution));
+            let mut game = builder.build().unwrap();
+            assert!(solver.play(&mut game).unwrap().won(), "{solver:?}");
+        }
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_solvers_play_german() {
+    let wl = BuiltinWList::german(5);
+    let solvers = [
+        AnyBuiltinSolver::Naive(NaiveSolver::build(&wl).unwrap()),
+        AnyBuiltinSolver::Entropy(EntropySolver::build(&wl).unwrap()),
+    ];
+    for solver in solvers {
+        for solution in ["würde", "größe", "hölle"] {
+            let builder = Game::builder(&wl).solution(wl.get_word(&solution.parse().unwrap()));
+            let mut game = builder.build().unwrap();
+            let response = solver.play(&mut game).unwrap();
+            assert!(response.won(), "{solver:?} did not find {solution}");
+        }
+    }
+}
+
+#[test]
+fn test_entropy_solver_opening_needs_all_candidates() {
+    let wl = small_wordlist();
+    let game = Game::builder(&wl).build().unwrap();
+    let opening = EntropySolver::build(&wl).unwrap().guess_for(&game).unwrap();
+
+    let solver = EntropySolver::build(&wl).unwrap();
+    let mut narrowed: Vec<_> = wl
+        .solutions()
+        .into_iter()
+        .filter(|s| s.0 != opening)
+        .collect();
+    narrowed.sort_by_key(|c| c.0);
+    narrowed.truncate(2);
+    let guess = solver.guess_for_candidates(&game, &narrowed).unwrap();
+    // with two candidates, guessing one of them is best
+    assert!(narrowed.iter().any(|c| c.0 == guess));
+    assert_eq!(solver.guess_for(&game).unwrap(), opening);
+    let all = wl.solutions();
+    assert_eq!(solver.guess_for_candidates(&game, &all).unwrap(), opening);
+}
+
+#[test]
+fn test_entropy_solver_plays_two_lengths() {
+    let wl = MixedWList::of(&[BuiltinWList::german(5), BuiltinWList::german(6)]);
+    let solver = EntropySolver::build(&wl).unwrap();
+    for (length, solution) in [(5, "würde"), (6, "morgen"), (5, "leben"), (6, "wieder")] {
+        let solution = wl.get_word(&solution.parse().unwrap());
+        let builder = Game::builder(&wl)
+            .length(length)
+            .max_steps(30)
+            .solution(solution);
+        let mut game = builder.build().unwrap();
+        assert_eq!(solver.guess_for(&game).unwrap().char_len(), length);
+        assert!(solver.play(&mut game).unwrap().won(), "{solution:?}");
+    }
+}
+
+#[test]
+fn test_decision_tree_plays_all_solutions() {
+    let wl = small_wordlist();
+    let config = TreeConfig {
+        objective: Objective::Expected,
+        breadth: None,
+        hard_mode: false,
+    };
+    // the default searches all guesses, so the tree is optimal
+    assert_eq!(TreeConfig::default(), config);
+    let exact = DecisionTree::build(&wl, 5, config.clone()).unwrap();
+    let narrow = DecisionTree::build(
+        &wl,
+        5,
+        TreeConfig {
+            breadth: Some(1),
+            ..config
+        },
+    )
+    .unwrap();
+    assert!(exact.cost().sum <= narrow.cost().sum);
+
+    let solver = DecisionTreeSolver::build(&wl)
+        .unwrap()
+        .breadth(None)
+        .with_tree(exact.clone())
+        .unwrap();
+    let mut total = 0;
+    for solution in wl.solutions() {
+        let builder = Game::builder(&wl).solution(Some(solution));
+        let mut game = builder.build().unwrap();
+        assert!(solver.play(&mut game).unwrap().won());
+        assert!(game.responses().len() <= exact.worst_case());
+        total += game.responses().len();
+    }
+    assert_eq!(total, exact.cost().sum);
+}
+
+#[test]
+fn test_decision_tree_plays_two_lengths() {
+    let dir = std::env::temp_dir().join(format!("wordle-tree-lengths-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("six.txt");
+    std::fs::write(
+        &path,
+        "planet plants slates crates traces garden harden master faster poster \
+         silver sliver listen silent tinsel enlist",
+    )
+    .unwrap();
+    let wl = MixedWList::of(&[small_wordlist(), BuiltinWList::load(&path, 6).unwrap()]);
+    let solver = DecisionTreeSolver::build(&wl).unwrap();
+    for length in [5, 6, 5] {
+        let root = *solver.tree(length).unwrap().root().guess();
+        assert_eq!(root.char_len(), length);
+        for solution in wl
+            .solutions()
+            .into_iter()
+            .filter(|s| s.0.char_len() == length)
+        {
+            let builder = Game::builder(&wl).length(length).solution(Some(solution));
+            let mut game = builder.build().unwrap();
+            assert!(solver.play(&mut game).unwrap().won(), "{}", solution.0);
+            assert_eq!(*game.responses()[0].guess(), root);
+        }
+    }
+
+    // leave the tree with the first guess, the following guesses come from a new subtree
+    let root = *solver.tree(6).unwrap().root().guess();
+    let detour: Word = if root == "poster" { "tinsel" } else { "poster" }
+        .parse()
+        .unwrap();
+    for solution in ["silent", "master", "garden"] {
+        let builder = Game::builder(&wl)
+            .length(6)
+            .max_steps(10)
+            .solution(wl.get_word(&solution.parse().unwrap()));
+        let mut game = builder.build().unwrap();
+        game.guess(&detour, None).unwrap();
+        assert!(solver.play(&mut game).unwrap().won(), "{solution}");
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_decision_tree_worst_case() {
+    let wl = small_wordlist();
+    let expected = DecisionTree::build(
+        &wl,
+        5,
+        TreeConfig {
+            objective: Objective::Expected,
+            breadth: None,
+            hard_mode: false,
+        },
+    )
+    .unwrap();
+    let worst = DecisionTree::build(
+        &wl,
+        5,
+        TreeConfig {
+            objective: Objective::WorstCase,
+            breadth: None,
+            hard_mode: false,
+        },
+    )
+    .unwrap();
+    assert!(worst.worst_case() <= expected.worst_case());
+    assert!(worst.cost().sum >= expected.cost().sum);
+}
+
+#[test]
+fn test_decision_tree_cache() {
+    let wl = small_wordlist();
+    let dir = std::env::temp_dir().join("wordle-analyzer-test-tree-cache");
+    let _ = std::fs::remove_dir_all(&dir);
+    let solver = DecisionTreeSolver::build(&wl).unwrap().cache_dir(&dir);
+    let tree = solver.tree(5).unwrap().clone();
+    let path = dir.join(DecisionTree::cache_file_name(&wl, 5, tree.config()));
+    assert!(path.exists());
+    assert_eq!(DecisionTree::load(&path).unwrap(), tree);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_solvers_play_hard_mode() {
+    let wl = small_wordlist();
+    let solvers: Vec<AnyBuiltinSolver<'_, BuiltinWList>> = vec![
+        NaiveSolver::build(&wl).unwrap().into(),
+        StupidSolver::build(&wl).unwrap().into(),
+        EntropySolver::build(&wl).unwrap().into(),
+        DecisionTreeSolver::build(&wl)
+            .unwrap()
+            .hard_mode(true)
+            .into(),
+        // leaves the tree whenever it would break the rules
+        DecisionTreeSolver::build(&wl).unwrap().into(),
+    ];
+    for solver in solvers {
+        for solution in wl.solutions() {
+            let builder = Game::builder(&wl)
+                .hard_mode(true)
+                .max_steps(30)
+                .solution(Some(solution));
+            let mut game = builder.build().unwrap();
+            assert!(solver.play(&mut game).unwrap().won(), "{solver:?}");
+        }
+    }
+}
+
+#[test]
+fn test_decision_tree_hard_mode() {
+    let wl = small_wordlist();
+    let config = TreeConfig {
+        objective: Objective::Expected,
+        breadth: None,
+        hard_mode: false,
+    };
+    let normal = DecisionTree::build(&wl, 5, config.clone()).unwrap();
+    let hard = DecisionTree::build(
+        &wl,
+        5,
+        TreeConfig {
+            hard_mode: true,
+            ..config
+        },
+    )
+    .unwrap();
+    // hard mode only takes options away
+    assert!(hard.cost().sum >= normal.cost().sum);
+}
+
+#[test]
+fn test_solvers_beat_adversary() {
+    let wl = small_wordlist();
+    let tree = DecisionTree::build(&wl, 5, TreeConfig::default()).unwrap();
+    let solvers: Vec<AnyBuiltinSolver<'_, BuiltinWList>> = vec![
+        NaiveSolver::build(&wl).unwrap().into(),
+        EntropySolver::build(&wl).unwrap().into(),
+        DecisionTreeSolver::build(&wl)
+            .unwrap()
+            .with_tree(tree.clone())
+            .unwrap()
+            .into(),
+    ];
+    for solver in solvers {
+        let builder = Game::builder(&wl).adversarial(true).max_steps(30);
+        let mut game = builder.build().unwrap();
+        assert!(solver.play(&mut game).unwrap().won(), "{solver:?}");
+        if let AnyBuiltinSolver::DecisionTree(_) = solver {
+            assert!(game.responses().len() <= tree.worst_case());
+        }
+    }
+}
+
+#[test]
+fn test_solvers_play_multiple_boards() {
+    let wl = small_wordlist();
+    let solvers: Vec<AnyBuiltinSolver<'_, BuiltinWList>> = vec![
+        NaiveSolver::build(&wl).unwrap().into(),
+        EntropySolver::build(&wl).unwrap().into(),
+        DecisionTreeSolver::build(&wl).unwrap().into(),
+    ];
+    for solver in solvers {
+        for _ in 0..5 {
+            let builder = Game::builder(&wl);
+            let mut game = builder.build_multi(4).unwrap();
+            solver.play_multi(&mut game).unwrap();
+            assert!(game.won(), "{solver:?}");
+            assert!(game.step() <= game.max_steps());
+        }
+    }
+}
+
+#[test]
+fn test_session_undo() {
+    let wl = wordlist();
+    let solver = EntropySolver::build(&wl).unwrap();
+    let game = Game::builder(&wl)
+        .solution(wl.get_word(&"sheep".parse().unwrap()))
+        .build()
+        .unwrap();
+    let mut session = Session::new(&solver, game);
+    let before = session.candidates().to_vec();
+    let first = session.suggest().unwrap();
+    session.make_a_move().unwrap();
+    let after_one = session.candidates().to_vec();
+    assert!(after_one.len() < before.len());
+    session.make_a_move().unwrap();
+    // the candidates are the same as if they were searched from scratch
+    assert_eq!(
+        session.candidates(),
+        wl.solutions_matching(session.game().constraints())
+    );
+    session.undo(1).unwrap();
+    assert_eq!(session.candidates(), after_one);
+    session.undo(1).unwrap();
+    assert_eq!(session.candidates(), before);
+    assert_eq!(session.suggest().unwrap(), first);
+    let response = session.play().unwrap();
+    assert!(response.won());
+    assert_eq!(session.candidates().len(), 1);
+}
+
+#[test]
+fn test_analysis() {
+    let wl = small_wordlist();
+    let solver = EntropySolver::build(&wl).unwrap();
+    for solution in wl.solutions().into_iter().take(5) {
+        let mut game = Game::builder(&wl).solution(Some(solution)).build().unwrap();
+        solver.play(&mut game).unwrap();
+        let analysis = Analysis::of(&solver, &game).unwrap();
+        assert!(analysis.won());
+        assert_eq!(analysis.steps().len(), game.responses().len());
+        for step in analysis.steps() {
+            // the solver is compared with itself
+            assert!(step.was_best());
+            assert_eq!(step.skill(), 1.0);
+            assert!(step.candidates_after() <= step.candidates_before());
+            assert!(step.information() >= 0.0);
+        }
+        assert_eq!(analysis.steps().last().unwrap().candidates_after(), 1);
+    }
+
+    // a game played elsewhere, only the evaluations are known
+    let wl = wordlist();
+    let solver = NaiveSolver::build(&wl).unwrap();
+    let mut game = GameBuilder::new(&wl, false).build().unwrap();
+    for (guess, eval) in [("slate", "xxcxc"), ("brace", "xccpc"), ("crane", "ccccc")] {
+        let guess = guess.parse().unwrap();
+        let eval = Evaluation::build(&guess, eval).unwrap();
+        game.guess(&guess, Some(eval)).unwrap();
+    }
+    let analysis = Analysis::of(&solver, &game).unwrap();
+    assert!(analysis.won());
+    assert_eq!(analysis.steps()[1].guess().as_str(), "brace");
+    assert_eq!(
+        analysis.steps()[1].candidates_before(),
+        analysis.steps()[0].candidates_after()
+    );
+    assert!(
+        (analysis.luck() - analysis.steps().iter().map(|s| s.luck()).sum::<f64>()).abs() < 1e-9
+    );
 }
@@ -0,0 +1,209 @@
+use test_log::test; // set the log level with an envvar: `RUST_LOG=trace cargo test`
+
+use wordle_analyzer::game::Game;
+use wordle_analyzer::solve::{NaiveSolver, Solver};
+use wordle_analyzer::wlist::builtin::BuiltinWList;
+use wordle_analyzer::wlist::format::{Format, Vocabulary, WordlistFormat};
+use wordle_analyzer::wlist::word::{transliterate, Alphabet, Word, MAX_WORD_BYTES};
+use wordle_analyzer::wlist::WordList;
+
+#[test]
+fn test_word_roundtrip() {
+    let word: Word = "crane".parse().unwrap();
+    assert_eq!(word.as_str(), "crane");
+    assert_eq!(word.to_string(), "crane");
+    assert_eq!(word, "crane");
+    assert_eq!(word.len(), 5);
+    let umlaut: Word = "größe".parse().unwrap();
+    assert_eq!(umlaut.chars().count(), 5);
+    assert_eq!(umlaut.as_str(), "größe");
+}
+
+#[test]
+fn test_word_too_long() {
+    assert!("a".repeat(MAX_WORD_BYTES).parse::<Word>().is_ok());
+    assert!("a".repeat(MAX_WORD_BYTES + 1).parse::<Word>().is_err());
+}
+
+#[test]
+fn test_word_order() {
+    let mut words: Vec<Word> = ["crate", "ab", "abc", "crane", "b"]
+        .iter()
+        .map(|w| w.parse().unwrap())
+        .collect();
+    words.sort();
+    let words: Vec<&str> = words.iter().map(Word::as_str).collect();
+    assert_eq!(words, ["ab", "abc", "b", "crane", "crate"]);
+}
+
+#[test]
+fn test_word_serde() {
+    let word: Word = "crane".parse().unwrap();
+    let json = serde_json::to_string(&word).unwrap();
+    assert_eq!(json, "\"crane\"");
+    assert_eq!(serde_json::from_str::<Word>(&json).unwrap(), word);
+}
+
+#[test]
+fn test_builtin_lookup() {
+    let wl = BuiltinWList::default();
+    let word: Word = "crane".parse().unwrap();
+    assert_eq!(wl.get_word(&word).unwrap().0, word);
+    assert!(wl.get_word(&"xxxxx".parse().unwrap()).is_none());
+}
+
+#[test]
+fn test_separate_solutions() {
+    let wl = BuiltinWList::load("data/wordlists/test_split.json", 5).unwrap();
+    assert!(wl.has_explicit_solutions());
+    assert_eq!(wl.wordmap().len(), 30);
+    assert_eq!(wl.len(), 7);
+    let solutions: Vec<Word> = wl.solutions().into_iter().map(|s| s.0).collect();
+    // uncommon words can be solutions and common words can be only guesses
+    assert!(solutions.contains(&"lymph".parse().unwrap()));
+    assert!(!solutions.contains(&"slate".parse().unwrap()));
+    for _ in 0..20 {
+        assert!(solutions.contains(&wl.rand_solution(5).unwrap().0));
+    }
+
+    let mut game = Game::builder(&wl)
+        .solution(wl.get_word(&"lymph".parse().unwrap()))
+        .build()
+        .unwrap();
+    game.guess(&"slate".parse().unwrap(), None).unwrap();
+    assert!(game.guess(&"xxxxx".parse().unwrap(), None).is_err());
+    let candidates = wl.solutions_matching(game.constraints());
+    assert!(candidates.iter().all(|c| solutions.contains(&c.0)));
+    let response = NaiveSolver::build(&wl).unwrap().play(&mut game).unwrap();
+    assert!(response.won());
+
+    let flat = BuiltinWList::load("data/wordlists/test_small.json", 5).unwrap();
+    assert!(!flat.has_explicit_solutions());
+    assert!(flat.with_solutions(["xxxxx".parse().unwrap()]).is_err());
+}
+
+#[test]
+fn test_load_split() {
+    let path = std::env::temp_dir().join(format!("wordle-solutions-{}.txt", std::process::id()));
+    std::fs::write(&path, "CRANE\nplant table\nlonger\n").unwrap();
+    let wl = BuiltinWList::load_split("data/wordlists/test_small.json", &path, 5).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let solutions: Vec<String> = wl.solutions().iter().map(|s| s.0.to_string()).collect();
+    assert_eq!(solutions, ["crane", "plant", "table"]);
+}
+
+#[test]
+fn test_priors() {
+    let wl = BuiltinWList::load("data/wordlists/test_small.json", 5).unwrap();
+    let wmap = wl.wordmap();
+    assert_eq!(wmap.freq_range(), 1e-9..=0.001);
+    assert_eq!(wmap.n_common(), 25);
+    assert_eq!(wmap.threshold(), 0.001);
+
+    let common = wl.prior(&"crane".parse().unwrap());
+    let rare = wl.prior(&"lymph".parse().unwrap());
+    assert!(common > 0.5 && rare < 0.5);
+    assert_eq!(common, wl.prior(&"slate".parse().unwrap()));
+    assert_eq!(rare, wl.prior(&"beast".parse().unwrap()));
+    assert_eq!(wl.prior(&"xxxxx".parse().unwrap()), 0.0);
+    assert_eq!(wl.len(), 25);
+    assert!(wl.solutions().iter().all(|s| wl.prior(&s.0) > 0.5));
+
+    let wl = BuiltinWList::default();
+    let priors = wl.priors();
+    let mut ranked = wl.sort_likelihood();
+    ranked.dedup_by(|a, b| a.1 == b.1);
+    assert!(ranked.windows(2).all(|w| priors[&w[0].0] > priors[&w[1].0]));
+    assert_eq!(wl.len(), priors.values().filter(|p| **p > 0.5).count());
+
+    let split = BuiltinWList::load("data/wordlists/test_split.json", 5).unwrap();
+    assert_eq!(split.prior(&"lymph".parse().unwrap()), 1.0);
+    assert_eq!(split.prior(&"slate".parse().unwrap()), 0.0);
+}
+
+#[test]
+fn test_wordlist_formats() {
+    let freq = |v: &Vocabulary, w: &str| v.words.get(w).unwrap().1;
+
+    let plain = Format::Plain
+        .parse("# a comment\ncrane slate\nTABLE\n")
+        .unwrap();
+    assert_eq!(plain.words.len(), 3);
+    assert_eq!(freq(&plain, "table"), freq(&plain, "crane"));
+
+    let ranked = Format::Ranked.parse("crane\nslate\ntable\n").unwrap();
+    assert!(freq(&ranked, "crane") > freq(&ranked, "slate"));
+    assert!(freq(&ranked, "slate") > freq(&ranked, "table"));
+
+    let counts = Format::Tsv
+        .parse("word\tcount\ncrane\t30\nslate\t10\n")
+        .unwrap();
+    assert_eq!(freq(&counts, "crane"), 0.75);
+    let frequencies = Format::Csv.parse("crane,0.002\nslate,0.001\n").unwrap();
+    assert_eq!(freq(&frequencies, "slate"), 0.001);
+    assert!(Format::Csv.parse("crane,many\nslate,some\n").is_err());
+
+    let split = Format::Json
+        .parse(r#"{"guesses": {"crane": 0.1, "slate": 0.2}, "solutions": ["crane"]}"#)
+        .unwrap();
+    assert_eq!(split.solutions.unwrap().len(), 1);
+
+    assert_eq!(Format::detect("{\"crane\": 0.1}"), Format::Json);
+    assert_eq!(Format::detect("crane\t3\n"), Format::Tsv);
+    assert_eq!(Format::detect("crane,3\n"), Format::Csv);
+    assert_eq!(Format::detect("crane\nslate\n"), Format::Plain);
+    assert!("yaml".parse::<Format>().is_err());
+}
+
+#[test]
+fn test_select_wordlist() {
+    let dir = std::env::temp_dir().join(format!("wordle-formats-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let guesses = dir.join("guesses.tsv");
+    let solutions = dir.join("solutions");
+    std::fs::write(&guesses, "crane\t5\nslate\t3\ntable\t1\nlonger\t9\n").unwrap();
+    std::fs::write(&solutions, "crane\ntable\n").unwrap();
+
+    let wl = BuiltinWList::select(guesses.to_str().unwrap(), 5).unwrap();
+    assert_eq!(wl.wordmap().len(), 3);
+    let wl =
+        BuiltinWList::select(&format!("{},{}", guesses.display(), solutions.display()), 5).unwrap();
+    assert!(wl.has_explicit_solutions());
+    assert_eq!(wl.len(), 2);
+    let wl = BuiltinWList::select(&format!("ranked:{}", solutions.display()), 5).unwrap();
+    assert!(wl.prior(&"crane".parse().unwrap()) >= wl.prior(&"table".parse().unwrap()));
+    assert_eq!(
+        BuiltinWList::select("eng", 5).unwrap().name(),
+        "(builtin english)"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_unicode_words() {
+    let word: Word = "GRÖẞE".parse().unwrap();
+    assert_eq!(word, "größe");
+    assert_eq!(word.char_len(), 5);
+    assert_eq!(word.len(), 7);
+    // a combining diaeresis is the same letter as the precomposed umlaut
+    assert_eq!("Ba\u{308}ren".parse::<Word>().unwrap(), "bären");
+    assert_eq!(transliterate("Füße"), "fuesse");
+    assert_eq!(Alphabet::Latin.word("Tür").unwrap(), "tuer");
+
+    let merged = Format::Json.parse(r#"{"Haus": 0.1, "haus": 0.2}"#).unwrap();
+    assert_eq!(merged.words.len(), 1);
+    assert!((merged.words.get("haus").unwrap().1 - 0.3).abs() < 1e-9);
+
+    let wl = BuiltinWList::german(5);
+    assert!(wl.get_word(&"würde".parse().unwrap()).is_some());
+    assert!(wl.wordmap().keys().all(|w| w.char_len() == 5));
+
+    let latin = BuiltinWList::select_with("ger", 5, Alphabet::Latin).unwrap();
+    assert!(latin
+        .wordmap()
+        .keys()
+        .all(|w| w.is_ascii() && w.char_len() == 5));
+    assert!(latin.get_word(&"muede".parse().unwrap()).is_some());
+    assert!(latin.get_word(&"würde".parse().unwrap()).is_none());
+}